@@ -0,0 +1,17 @@
+// Copyright Rob Gage 2025
+
+use crate::SyntaxTag;
+
+/// Information that a combinator attaches to the parser it applies, which a `Mode` can observe
+/// through `Mode::apply_annotated`
+///
+/// New kinds of annotations may be added, so modes should apply parsers with annotations they do
+/// not recognize as nested parsers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Annotation {
+    /// The parser has a role in concrete syntax trees
+    Syntax (SyntaxTag),
+    /// The parser is traced with a name
+    Traced (&'static str),
+}
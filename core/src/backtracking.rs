@@ -0,0 +1,83 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Mode,
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+};
+
+/// Policies for the messages of parser applications that are abandoned when a combinator like
+/// `choice` or `optional` backtracks
+///
+/// `choice` discards these messages and `optional` keeps them unless configured otherwise. An
+/// `optional` parser never fails, so `KeepOnFailure` discards its messages like `Discard`. A
+/// `Recoverable` parser always keeps the messages of the parser it recovers from, since reporting
+/// them is the purpose of recovery.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Backtracking {
+    /// Messages from abandoned applications are kept alongside the final result
+    Keep,
+    /// Messages from abandoned applications are dropped
+    #[default]
+    Discard,
+    /// Messages from abandoned applications are kept only if the combinator fails, so that they
+    /// accompany its error
+    KeepOnFailure,
+}
+
+impl Backtracking {
+
+    /// Stores the messages of an abandoned parser application according to this policy
+    pub(crate) fn abandon<_Mode: Mode, M>(
+        &self,
+        abandoned: _Mode::MessageContainer<M>,
+        messages: _Mode::MessageContainer<M>,
+    ) -> _Mode::MessageContainer<M> {
+        match self {
+            Backtracking::Discard => abandoned,
+            _ => _Mode::merge_message_containers(abandoned, messages),
+        }
+    }
+
+    /// Adds the stored messages of abandoned parser applications to the final result of a
+    /// combinator according to this policy
+    pub(crate) fn resolve<O, E, M, _Mode: Mode>(
+        &self,
+        abandoned: _Mode::MessageContainer<M>,
+        result: ModeResult<O, E, M, _Mode>,
+    ) -> ModeResult<O, E, M, _Mode> {
+        match (self, result) {
+            (Backtracking::Keep, Success (output, messages)) => Success (
+                output,
+                _Mode::merge_message_containers(abandoned, messages)
+            ),
+            (Backtracking::Keep | Backtracking::KeepOnFailure, Failure (error, messages))
+                => Failure (error, _Mode::merge_message_containers(abandoned, messages)),
+            (_, result) => result,
+        }
+    }
+
+    /// Adds the stored messages of abandoned parser applications to the final result of a
+    /// combinator, attaching them to its error as notes created by a function if one is given
+    pub(crate) fn resolve_noted<O, E, M, _Mode: Mode>(
+        &self,
+        abandoned: _Mode::MessageContainer<M>,
+        result: ModeResult<O, E, M, _Mode>,
+        note: Option<&dyn Fn(M) -> M>,
+    ) -> ModeResult<O, E, M, _Mode> {
+        let Some (note) = note else { return self.resolve(abandoned, result) };
+        match result {
+            Failure (error, messages) => {
+                let noted: ModeResult<(), (), M, _Mode>
+                    = _Mode::map_messages(Success (_Mode::convert_output(()), abandoned), note);
+                let (Success (_, notes) | Failure (_, notes)) = noted;
+                Failure (error, _Mode::merge_message_containers(notes, messages))
+            }
+            success => success,
+        }
+    }
+
+}
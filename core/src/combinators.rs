@@ -1,13 +1,13 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Doc,
+    HighlightClass,
     Input,
     parsers::*,
-    Check,
-    ModeResult,
-    Verbose
+    Printer,
+    Span,
 };
-use std::fmt::Debug;
 
 /// Methods implemented for all parsers that allow easy construction of parser combinators
 pub trait Combinators<'a, O, E, M, I>
@@ -16,6 +16,17 @@ where
     I: Input<'a>
 {
 
+    /// Applies this parser only if another parser does not match at the same position, otherwise
+    /// failing with an error created from the span of input that the other parser matches
+    fn and_is_not<P, _O>(
+        self,
+        other: P,
+        error: impl Fn(Span) -> E,
+    ) -> impl Parser<'a, O, E, M, I>
+    where
+        P: Parser<'a, _O, E, M, I>
+    { preceded(not(other, error), self) }
+
     /// Boxes a parser, performing type erasure so it can be used in combinators like `choice`
     fn boxed(self) -> Box<dyn Parser<'a, O, E, M, I> + 'a>
     { boxed(self) }
@@ -30,17 +41,55 @@ where
         P: Parser<'a, O, E, M, I>
     { recoverable(self, fallback) }
 
+    /// Emits a message whenever this parser succeeds
+    fn deprecated(
+        self,
+        message: M
+    ) -> impl Parser<'a, O, E, M, I>
+    where
+        M: Clone,
+    { deprecated(self, message) }
+
     /// Replaces a parser's output
     fn emit<_O>(
         self,
         output: _O
-    ) -> impl Parser<'a, _O, E, M, I>
+    ) -> Emitting<O, _O, Self>
     where
         _O: Clone,
     { emitting(self, output) }
 
+    /// Fails if this parser's output does not satisfy a predicate, with an error labelled with what
+    /// was expected
+    fn filter(
+        self,
+        label: &'static str,
+        predicate: impl Fn(&O) -> bool
+    ) -> impl Parser<'a, O, E, M, I>
+    where
+        E: From<Rejected>,
+    { filter(self, label, predicate) }
+
+    /// Lays out the documents that this parser prints using a function, like `Doc::group`
+    fn format(self, format: fn(Doc) -> Doc) -> Formatted<Self>
+    { formatted(self, format) }
+
+    /// Highlights the input this parser consumes with a class, making it a token of concrete syntax
+    /// trees
+    fn highlight(self, class: HighlightClass) -> Tagged<Self>
+    { highlighted(self, class) }
+
+    /// Names this parser, making it a rule of the grammar that describes the parsers it is part of
+    /// and a node of concrete syntax trees
+    fn labelled(self, name: &'static str) -> Labelled<Self>
+    { labelled(self, name) }
+
+    /// Remembers this parser's results with a key in inputs that keep a `MemoTable`, so they can be
+    /// reused
+    fn memoize(self, key: &'static str) -> Memoized<Self> { memoized(self, key) }
+
     /// Applies a parser optionally, returning `None` instead of an error if it fails
-    fn or_not(self) -> impl Parser<'a, Option<O>, E, M, I>
+    fn or_not(self) -> Optional<Self>
     { optional(self) }
 
     /// Maps a parser's output to another type using a function
@@ -57,6 +106,19 @@ where
     ) -> impl Parser<'a, O, _E, M, I>
     { mapped_error(self, f) }
 
+    /// Maps a parser's output to another type using a function, and maps outputs back to values
+    /// that this parser can print using an inverse function that can fail
+    fn map_invertible<_O, OC, F, G>(
+        self,
+        f: F,
+        inverse: G,
+    ) -> Invertible<O, OC, F, G, Self>
+    where
+        F: Fn(O) -> _O,
+        G: Fn(&_O) -> Option<OC>,
+        Self: Printer<OC>,
+    { invertible(self, f, inverse) }
+
     /// Maps a parser's messages to another type using a function
     fn map_messages<_M>(
         self,
@@ -64,11 +126,47 @@ where
     ) -> impl Parser<'a, O, E, _M, I>
     { mapped_messages(self, f) }
 
+    /// Applies this parser without consuming any input, restoring the cursor afterwards
+    fn rewind(self) -> impl Parser<'a, O, E, M, I>
+    { lookahead(self) }
+
+    /// Makes the input this parser consumes a single token of a kind in concrete syntax trees
+    fn syntax_token(self, kind: &'static str) -> Tagged<Self>
+    { syntax_token(self, kind) }
+
+    /// Makes the input this parser consumes a single trivia token of a kind, like a comment, in
+    /// concrete syntax trees
+    fn trivia(self, kind: &'static str) -> Tagged<Self>
+    { trivia(self, kind) }
+
+    /// Maps this parser's output and span to another type using a function that can fail
+    fn try_map<_O>(
+        self,
+        f: impl Fn(O, Span) -> Result<_O, E>
+    ) -> impl Parser<'a, _O, E, M, I>
+    { try_map(self, f) }
+
+    /// Validates this parser's output and span using a function that maps it and can emit
+    /// messages
+    fn validate<_O>(
+        self,
+        f: impl Fn(O, Span, &mut Emitter<M>) -> _O
+    ) -> impl Parser<'a, _O, E, M, I>
+    { validate(self, f) }
+
+    /// Emits a message created from this parser's output if the output satisfies a predicate
+    fn warn_if(
+        self,
+        predicate: impl Fn(&O) -> bool,
+        message: impl Fn(&O) -> M
+    ) -> impl Parser<'a, O, E, M, I>
+    { warn_if(self, predicate, message) }
+
     /// Applies another parser in sequence after this one, and returns both results as a tuple
     fn then<P, _O>(
         self,
         next: P
-    ) -> impl Parser<'a, (O, _O), E, M, I>
+    ) -> Sequenced<Self, P>
     where
         P: Parser<'a, _O, E, M, I>
     { sequenced(self, next) }
@@ -77,7 +175,7 @@ where
     fn then_ignore<P, _O>(
         self,
         next: P
-    ) -> impl Parser<'a, O, E, M, I>
+    ) -> Terminated<_O, Self, P>
     where
         P: Parser<'a, _O, E, M, I>
     { terminated(self, next) }
@@ -86,17 +184,13 @@ where
     fn ignore_then<P, _O>(
         self,
         next: P
-    ) -> impl Parser<'a, _O, E, M, I>
+    ) -> Preceded<O, Self, P>
     where
         P: Parser<'a, _O, E, M, I>
     { preceded(self, next) }
 
-    /// Applies a parser optionally, returning `None` instead of an error if it fails
-    fn trace(self, name: &'static str) -> impl Parser<'a, O, E, M, I>
-    where
-        O: Debug,
-        E: Debug,
-    { traced(self, name) }
+    /// Traces this parser with a name, which identifies it in `Trace` mode and `tracing` spans
+    fn trace(self, name: &'static str) -> impl Parser<'a, O, E, M, I> { traced(self, name) }
 
 }
 
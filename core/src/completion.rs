@@ -0,0 +1,212 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Grammar,
+    Input,
+    Mode,
+    modes::from_check,
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+    Parser,
+};
+use std::cell::RefCell;
+
+thread_local! {
+    /// The query of the innermost `Completion::run` on this thread, if one is running
+    static QUERY: RefCell<Option<Query>> = const { RefCell::new(None) };
+}
+
+
+/// What is expected at the offset queried by a `Completion::run` so far
+struct Query {
+    /// Whether the parser being applied is nested in one applied at the offset whose grammar
+    /// already describes it
+    covered: bool,
+    /// What is expected at the offset, in order of discovery
+    expected: Vec<Expected>,
+    /// The offset of the input that is queried
+    offset: usize,
+}
+
+
+/// Something that a parser expects to find at a position of its input
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Expected {
+    /// A labelled parser
+    Label (&'static str),
+    /// Input described in words, like a number or an identifier
+    Special (&'static str),
+    /// Literal input
+    Token (String),
+}
+
+
+/// Parser mode that only ensures the parser matches the input like `Check`, while collecting what
+/// could be parsed at an offset of the input
+///
+/// Expectations are collected with `Completion::run`. Every parser applied at the offset adds the
+/// labels, literal tokens and special input that its grammar can start with, so the alternatives of
+/// `Choice`, `Optional` and `Iterated` parsers that are alive at the offset all contribute,
+/// whether or not they end up matching the input after it. Parsers that consume input past the
+/// offset, like optional whitespace, hide what could follow them, so the input usually ends at the
+/// offset.
+pub struct Completion;
+
+impl Completion {
+
+    /// Applies a parser in this mode, returning everything that could be parsed at an offset of the
+    /// input in order of discovery
+    pub fn run<'a, O, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+        offset: usize,
+    ) -> Vec<Expected>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        // keep the query of an enclosing run separate so runs can be nested
+        let query: Query = Query { covered: false, expected: Vec::new(), offset };
+        let enclosing: Option<Query> = QUERY.replace(Some (query));
+        let _: ModeResult<O, E, M, Self> = Self::apply_nested(parser, input);
+        QUERY.replace(enclosing).expect("the query is answered during the run").expected
+    }
+
+}
+
+/// Adds what a grammar can start with to a list without repeating anything, returning `true` if
+/// the grammar can also match without consuming input
+///
+/// Grammars that are not known cannot be described, so `opaque` is set if the grammar can start
+/// with one.
+fn collect_expected(grammar: &Grammar, expected: &mut Vec<Expected>, opaque: &mut bool) -> bool {
+    match grammar {
+        Grammar::Choice (alternatives) => alternatives.iter().fold(false, |empty, alternative| {
+            collect_expected(alternative, expected, opaque) | empty
+        }),
+        Grammar::Empty | Grammar::Lookahead (_) | Grammar::Not (_) => true,
+        Grammar::End => false,
+        Grammar::Unknown => {
+            *opaque = true;
+            false
+        }
+        Grammar::Optional (grammar) => {
+            collect_expected(grammar, expected, opaque);
+            true
+        }
+        Grammar::Permutation (grammars) => grammars.iter()
+            .fold(true, |empty, grammar| collect_expected(grammar, expected, opaque) & empty),
+        Grammar::Repeated { item, minimum, .. }
+            => collect_expected(item, expected, opaque) || *minimum == 0,
+        // a recursive rule is described where it encloses its references, so only its name is new
+        Grammar::Reference (name) => {
+            add_expected(Expected::Label (name), expected);
+            false
+        }
+        Grammar::Rule (name, body) => {
+            add_expected(Expected::Label (name), expected);
+            collect_expected(body, expected, opaque)
+        }
+        Grammar::Sequence (items) => items.iter()
+            .all(|item| collect_expected(item, expected, opaque)),
+        Grammar::Special (description) => {
+            add_expected(Expected::Special (description), expected);
+            false
+        }
+        Grammar::Terminal (text) => {
+            add_expected(Expected::Token (text.clone()), expected);
+            false
+        }
+    }
+}
+
+/// Adds something that is expected to a list if it is not in the list yet
+fn add_expected(item: Expected, expected: &mut Vec<Expected>) {
+    if !expected.contains(&item) { expected.push(item) }
+}
+
+
+impl Mode for Completion {
+
+    type OutputForm<O> = ();
+
+    type ErrorForm<E> = ();
+
+    type MessageContainer<M> = ();
+
+    fn apply_parser<'a, O, E, M, I, P>(parser: &P, input: &'a I) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I> + ?Sized,
+    { from_check(parser.apply_completion(input)) }
+
+    fn observes_nested() -> bool { true }
+
+    fn apply_nested<'a, O, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+    ) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        let position: usize = input.store_cursor();
+        // the grammar of the outermost parser applied at the offset describes the parsers nested
+        // in it there, unless part of it is opaque, so only that parser is described
+        let described: bool = QUERY.with_borrow_mut(|query| match query {
+            Some (query) if query.offset == position && !query.covered => {
+                let mut opaque: bool = false;
+                collect_expected(&parser.describe(), &mut query.expected, &mut opaque);
+                query.covered = !opaque;
+                true
+            }
+            _ => false,
+        });
+        let result: ModeResult<O, E, M, Self> = parser.apply::<Self>(input);
+        if described {
+            QUERY.with_borrow_mut(|query| if let Some (query) = query { query.covered = false });
+        }
+        result
+    }
+
+    fn convert_output<O>(_: impl Into<O>) {}
+
+    fn convert_error<E>(_: impl Into<E>) {}
+
+    fn merge_outputs<OA1, OA2, OB>(_: (), _: (), _: impl Fn(OA1, OA2) -> OB) {}
+
+    fn merge_errors<EA1, EA2, EB>(_: (), _: (), _: impl Fn(EA1, EA2) -> EB) {}
+
+    fn merge_message_containers<M>(_: (), _: impl Into<()>) {}
+
+    fn map_output<OA, OB, E, M>(
+        result: ModeResult<OA, E, M, Self>,
+        _: impl Fn(OA) -> OB,
+    ) -> ModeResult<OB, E, M, Self> {
+        if result.is_success() { Success ((), ()) } else { Failure ((), ()) }
+    }
+
+    fn map_error<O, EA, EB, M>(
+        result: ModeResult<O, EA, M, Self>,
+        _: impl Fn(EA) -> EB,
+    ) -> ModeResult<O, EB, M, Self> {
+        if result.is_success() { Success ((), ()) } else { Failure ((), ()) }
+    }
+
+    fn map_messages<O, E, MA, MB>(
+        result: ModeResult<O, E, MA, Self>,
+        _: impl Fn(MA) -> MB,
+    ) -> ModeResult<O, E, MB, Self> {
+        if result.is_success() { Success ((), ()) } else { Failure ((), ()) }
+    }
+
+    fn new_message_container<M>() {}
+
+    fn add_message_to_container<M>(_: &mut (), _: M) {}
+
+    fn add_message_from_output<O, M>(_: &(), _: &mut (), _: impl FnOnce(&O) -> Option<M>) {}
+
+}
@@ -0,0 +1,382 @@
+// Copyright Rob Gage 2025
+
+mod tree;
+
+pub use tree::{
+    GreenElement,
+    GreenNode,
+    GreenToken,
+    SyntaxElement,
+    SyntaxNode,
+    SyntaxToken,
+};
+
+use crate::{
+    Annotation,
+    Emitter,
+    Input,
+    Mode,
+    modes::{
+        from_verbose,
+        validate_output,
+    },
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+    Parser,
+};
+use std::{
+    cell::RefCell,
+    sync::Arc,
+};
+
+thread_local! {
+    /// The syntax recorded by the innermost `Cst::run` on this thread, if one is running
+    static EVENTS: RefCell<Option<Vec<Event>>> = const { RefCell::new(None) };
+}
+
+
+/// The role of a parser in a concrete syntax tree
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SyntaxTag {
+    /// The parser's input becomes a node of a kind
+    Node (&'static str),
+    /// The parser's input becomes a single token of a kind
+    Token (&'static str),
+    /// The parser's input becomes a single trivia token of a kind
+    Trivia (&'static str),
+}
+
+
+/// A piece of syntax recorded while a parser is applied in `Cst` mode
+enum Event {
+    /// A node starts at a position
+    Open (&'static str, usize),
+    /// The innermost open node ends at a position
+    Close (usize),
+    /// A token covers the input between two positions
+    Token {
+        kind: &'static str,
+        start: usize,
+        end: usize,
+        trivia: bool,
+    },
+}
+
+impl Event {
+
+    /// The furthest position that this event covers
+    const fn end(&self) -> usize {
+        match self {
+            Event::Open (_, position) | Event::Close (position) => *position,
+            Event::Token { end, .. } => *end,
+        }
+    }
+
+}
+
+
+/// Parser mode that extracts all information from the parser's input like `Verbose`, while
+/// building a lossless concrete syntax tree of it
+///
+/// Trees are built with `Cst::run`. Labelled parsers become nodes, and parsers tagged with
+/// `syntax_token` or `trivia` become tokens. Input consumed without a tag becomes tokens
+/// automatically, with whitespace becoming trivia, and trivia at the edges of a node is attached to
+/// its parent instead, so every byte of the input is reproduced by the tree. Parsers behind a
+/// `dyn Parser` are applied verbosely, so their input only becomes untagged tokens.
+pub struct Cst;
+
+impl Cst {
+
+    /// The kind of the root node of every concrete syntax tree
+    pub const ROOT: &str = "root";
+
+    /// The kind of tokens made from input that was consumed without being tagged
+    pub const TOKEN: &str = "token";
+
+    /// The kind of the token made from input after the end of a parse
+    pub const UNPARSED: &str = "unparsed";
+
+    /// The kind of trivia tokens made from whitespace that was consumed without being tagged
+    pub const WHITESPACE: &str = "whitespace";
+
+    /// Applies a parser in this mode, returning its result and a concrete syntax tree of the whole
+    /// input from the cursor onwards
+    pub fn run<'a, O, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+    ) -> (ModeResult<O, E, M, Self>, SyntaxNode)
+    where
+        I: Input<'a, Slice = &'a str>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        let start: usize = input.store_cursor();
+        // keep the events of an enclosing run separate so runs can be nested
+        let enclosing: Option<Vec<Event>> = EVENTS.replace(Some (Vec::new()));
+        let result: ModeResult<O, E, M, Self> = Self::apply_nested(parser, input);
+        let events: Vec<Event> = EVENTS.replace(enclosing)
+            .expect("events are recorded during the run");
+        let end: usize = input.store_cursor();
+        while input.peek().is_some() { input.advance() }
+        let input_end: usize = input.store_cursor();
+        input.move_cursor(end);
+        let mut builder: Builder<'a, I> = Builder::new(input, start);
+        for event in events { builder.add(event) }
+        builder.gap(end);
+        if input_end > builder.position {
+            builder.token(Cst::UNPARSED, input.slice(builder.position, input_end), false);
+        }
+        let root: GreenNode = builder.finish();
+        (result, SyntaxNode::new_root(Arc::new(root), start))
+    }
+
+}
+
+/// Runs a function with the events recorded by the current `Cst::run`, if there is one
+fn with_events(function: impl FnOnce(&mut Vec<Event>)) {
+    EVENTS.with_borrow_mut(|events| if let Some (events) = events { function(events) });
+}
+
+/// Returns the number of events recorded by the current `Cst::run`
+fn event_count() -> usize {
+    EVENTS.with_borrow(|events| events.as_ref().map_or(0, Vec::len))
+}
+
+
+/// Builds a green tree from the events recorded by `Cst::run`
+struct Builder<'a, I> {
+    /// The input that the events were recorded from
+    input: &'a I,
+    /// The kinds and children of the nodes that are open, starting with the root
+    open: Vec<(&'static str, Vec<GreenElement>)>,
+    /// The position up to which the input is covered by the tree
+    position: usize,
+}
+
+impl<'a, I> Builder<'a, I>
+where
+    I: Input<'a, Slice = &'a str>,
+{
+
+    /// Creates a new `Builder` for a tree starting at a position
+    fn new(input: &'a I, position: usize) -> Self {
+        Self { input, open: vec![(Cst::ROOT, Vec::new())], position }
+    }
+
+    /// Adds an event to the tree
+    fn add(&mut self, event: Event) {
+        match event {
+            Event::Open (kind, start) => {
+                self.gap(start);
+                self.open.push((kind, Vec::new()));
+            }
+            Event::Close (end) => {
+                self.gap(end);
+                let (kind, mut children) = self.open.pop().expect("closed nodes were opened");
+                // trivia at the edges of a node belongs to its parent
+                let leading: usize = children.iter()
+                    .take_while(|child| child.is_trivia())
+                    .count();
+                let trailing: Vec<GreenElement> = match children[leading..].iter()
+                    .rposition(|child| !child.is_trivia())
+                {
+                    Some (last) => children.split_off(leading + last + 1),
+                    None => Vec::new(),
+                };
+                let parent: &mut Vec<GreenElement> = &mut self.open.last_mut()
+                    .expect("the root is never closed").1;
+                parent.extend(children.drain(..leading));
+                parent.push(GreenElement::Node (Arc::new(GreenNode::new(kind, children))));
+                parent.extend(trailing);
+            }
+            Event::Token { kind, start, end, trivia } => if start >= self.position {
+                self.gap(start);
+                self.token(kind, self.input.slice(start, end), trivia);
+                self.position = end;
+            },
+        }
+    }
+
+    /// Covers the input up to a position with untagged tokens, splitting out whitespace as trivia
+    fn gap(&mut self, end: usize) {
+        if end <= self.position { return }
+        let text: &'a str = self.input.slice(self.position, end);
+        let mut rest: &'a str = text;
+        while let Some (first) = rest.chars().next() {
+            let whitespace: bool = first.is_whitespace();
+            let length: usize = rest.find(|character: char| character.is_whitespace() != whitespace)
+                .unwrap_or(rest.len());
+            let kind: &'static str = if whitespace { Cst::WHITESPACE } else { Cst::TOKEN };
+            self.token(kind, &rest[..length], whitespace);
+            rest = &rest[length..];
+        }
+        self.position = end;
+    }
+
+    /// Adds a token to the innermost open node
+    fn token(&mut self, kind: &'static str, text: &str, trivia: bool) {
+        let token: GreenToken = GreenToken::new(kind, text, trivia);
+        self.open.last_mut().expect("the root is open").1
+            .push(GreenElement::Token (Arc::new(token)));
+    }
+
+    /// Finishes the tree, returning its root
+    fn finish(mut self) -> GreenNode {
+        // nodes left open by misbehaving parsers are closed where the tree ends
+        while self.open.len() > 1 { self.add(Event::Close (self.position)) }
+        let (kind, children) = self.open.pop().expect("the root is open");
+        GreenNode::new(kind, children)
+    }
+
+}
+
+
+impl Mode for Cst {
+
+    type OutputForm<O> = O;
+
+    type ErrorForm<E> = E;
+
+    type MessageContainer<M> = Vec<M>;
+
+    fn apply_parser<'a, O, E, M, I, P>(parser: &P, input: &'a I) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I> + ?Sized,
+    { from_verbose(parser.apply_cst(input)) }
+
+    fn observes_nested() -> bool { true }
+
+    fn apply_nested<'a, O, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+    ) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        let start: usize = event_count();
+        let result: ModeResult<O, E, M, Self> = parser.apply::<Self>(input);
+        let end: usize = input.store_cursor();
+        // syntax from failed applications, or from input that was given back, is not part of the
+        // tree
+        with_events(|events| if !result.is_success()
+            || events.last().is_some_and(|event| event.end() > end)
+        { events.truncate(start) });
+        result
+    }
+
+    fn apply_validated<'a, OA, OB, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+        function: impl FnOnce(OA, &mut Emitter<M>) -> Result<OB, E>,
+    ) -> ModeResult<OB, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, OA, E, M, I>,
+    { validate_output(parser, input, function) }
+
+    fn apply_annotated<'a, O, E, M, I, P>(
+        annotation: Annotation,
+        parser: &P,
+        input: &'a I,
+    ) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        let Annotation::Syntax (tag) = annotation else { return Self::apply_nested(parser, input) };
+        let start: usize = input.store_cursor();
+        let mark: usize = event_count();
+        if let SyntaxTag::Node (kind) = tag {
+            with_events(|events| events.push(Event::Open (kind, start)));
+        }
+        let result: ModeResult<O, E, M, Self> = Self::apply_nested(parser, input);
+        let end: usize = input.store_cursor();
+        with_events(|events| match (result.is_success(), tag) {
+            (false, _) => events.truncate(mark),
+            (true, SyntaxTag::Node (_)) => events.push(Event::Close (end)),
+            (true, SyntaxTag::Token (kind) | SyntaxTag::Trivia (kind)) => {
+                // a token has no syntax within it
+                events.truncate(mark);
+                if end > start {
+                    let trivia: bool = matches!(tag, SyntaxTag::Trivia (_));
+                    events.push(Event::Token { kind, start, end, trivia });
+                }
+            }
+        });
+        result
+    }
+
+    fn convert_output<O>(output: impl Into<O>) -> O { output.into() }
+
+    fn convert_error<E>(error: impl Into<E>) -> E  { error.into() }
+
+    fn merge_outputs<OA1, OA2, OB>(
+        output_1: OA1,
+        output_2: OA2,
+        function: impl Fn(OA1, OA2) -> OB
+    ) -> OB { function(output_1, output_2) }
+
+    fn merge_errors<EA1, EA2, EB>(
+        error_1: EA1,
+        error_2: EA2,
+        function: impl Fn(EA1, EA2) -> EB
+    ) -> EB { function(error_1, error_2) }
+
+    fn merge_message_containers<M>(
+        a: Vec<M>,
+        b: impl Into<Vec<M>>
+    ) -> Vec<M> {
+        let mut a: Vec<M> = a;
+        a.extend(b.into());
+        a
+    }
+
+    fn map_output<OA, OB, E, M>(
+        result: ModeResult<OA, E, M, Self>,
+        function: impl Fn(OA) -> OB,
+    ) -> ModeResult<OB, E, M, Self> {
+        match result {
+            Success (output, messages) => Success (function(output), messages),
+            Failure (error, messages) => Failure (error, messages),
+        }
+    }
+
+    fn map_error<O, EA, EB, M>(
+        result: ModeResult<O, EA, M, Self>,
+        function: impl Fn(EA) -> EB,
+    ) -> ModeResult<O, EB, M, Self> {
+        match result {
+            Success (output, messages) => Success (output, messages),
+            Failure (error, messages) => Failure (function(error), messages),
+        }
+    }
+
+    fn map_messages<O, E, MA, MB>(
+        result: ModeResult<O, E, MA, Self>,
+        function: impl Fn(MA) -> MB,
+    ) -> ModeResult<O, E, MB, Self> {
+        match result {
+            Success (output, messages) => Success (output, messages.into_iter()
+                .map(function).collect::<Vec<MB>>()),
+            Failure (error, messages) => Failure (error, messages.into_iter()
+                .map(function).collect::<Vec<MB>>()),
+        }
+    }
+
+    fn new_message_container<M>() -> Vec<M> { Vec::new() }
+
+    fn add_message_to_container<M>(container: &mut Vec<M>, message: M) { container.push(message) }
+
+    fn add_message_from_output<O, M>(
+        output: &O,
+        container: &mut Vec<M>,
+        function: impl FnOnce(&O) -> Option<M>,
+    ) {
+        if let Some (message) = function(output) { container.push(message) }
+    }
+
+}
@@ -0,0 +1,332 @@
+// Copyright Rob Gage 2025
+
+use crate::Span;
+use std::{
+    fmt::{
+        Debug,
+        Display,
+        Formatter,
+        Result as FormatResult,
+    },
+    rc::Rc,
+    sync::Arc,
+};
+
+
+/// An immutable node of a concrete syntax tree that knows its length but not its position, so it
+/// can be shared between trees
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct GreenNode {
+    /// The tokens and nodes within this node, in order
+    children: Vec<GreenElement>,
+    /// The kind of this node
+    kind: &'static str,
+    /// The length of the text covered by this node
+    length: usize,
+}
+
+impl GreenNode {
+
+    /// Creates a new `GreenNode` from a kind and its children
+    pub fn new(kind: &'static str, children: Vec<GreenElement>) -> Self {
+        let length: usize = children.iter().map(GreenElement::length).sum();
+        Self { children, kind, length }
+    }
+
+    /// The tokens and nodes within this node, in order
+    pub fn children(&self) -> &[GreenElement] { &self.children }
+
+    /// The kind of this node
+    pub const fn kind(&self) -> &'static str { self.kind }
+
+    /// The length of the text covered by this node
+    pub const fn length(&self) -> usize { self.length }
+
+    /// Writes the text covered by this node to a string
+    fn write_text(&self, text: &mut String) {
+        for child in &self.children {
+            match child {
+                GreenElement::Node (node) => node.write_text(text),
+                GreenElement::Token (token) => text.push_str(&token.text),
+            }
+        }
+    }
+
+}
+
+
+/// An immutable token of a concrete syntax tree, holding the text it covers
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct GreenToken {
+    /// The kind of this token
+    kind: &'static str,
+    /// The text covered by this token
+    text: String,
+    /// Whether this token is trivia, like whitespace or a comment
+    trivia: bool,
+}
+
+impl GreenToken {
+
+    /// Creates a new `GreenToken` from a kind and the text it covers
+    pub fn new(kind: &'static str, text: impl Into<String>, trivia: bool) -> Self {
+        Self { kind, text: text.into(), trivia }
+    }
+
+    /// Returns `true` if this token is trivia, like whitespace or a comment
+    pub const fn is_trivia(&self) -> bool { self.trivia }
+
+    /// The kind of this token
+    pub const fn kind(&self) -> &'static str { self.kind }
+
+    /// The length of the text covered by this token
+    pub fn length(&self) -> usize { self.text.len() }
+
+    /// The text covered by this token
+    pub fn text(&self) -> &str { &self.text }
+
+}
+
+
+/// A node or token of a green concrete syntax tree
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum GreenElement {
+    /// A node
+    Node (Arc<GreenNode>),
+    /// A token
+    Token (Arc<GreenToken>),
+}
+
+impl GreenElement {
+
+    /// Returns `true` if this element is a trivia token
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, GreenElement::Token (token) if token.is_trivia())
+    }
+
+    /// The length of the text covered by this element
+    pub fn length(&self) -> usize {
+        match self {
+            GreenElement::Node (node) => node.length(),
+            GreenElement::Token (token) => token.length(),
+        }
+    }
+
+}
+
+
+/// A node of a concrete syntax tree that knows its position and its parent
+///
+/// Syntax nodes are created on demand from a `GreenNode` and are cheap to clone.
+#[derive(Clone)]
+pub struct SyntaxNode (Rc<NodeData>);
+
+/// The data of a `SyntaxNode`
+struct NodeData {
+    /// The green node that this node is a view of
+    green: Arc<GreenNode>,
+    /// The position of this node among the children of its parent
+    index: usize,
+    /// The position where this node starts
+    offset: usize,
+    /// The node that this node is a child of, if it is not the root
+    parent: Option<SyntaxNode>,
+}
+
+impl SyntaxNode {
+
+    /// Creates the root `SyntaxNode` of a tree starting at a position
+    pub fn new_root(green: Arc<GreenNode>, offset: usize) -> Self {
+        Self (Rc::new(NodeData { green, index: 0, offset, parent: None }))
+    }
+
+    /// The nodes within this node, in order
+    pub fn children(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.children_with_tokens().filter_map(|child| match child {
+            SyntaxElement::Node (node) => Some (node),
+            SyntaxElement::Token (_) => None,
+        })
+    }
+
+    /// The tokens and nodes within this node, in order
+    pub fn children_with_tokens(&self) -> impl Iterator<Item = SyntaxElement> + '_ {
+        let mut offset: usize = self.0.offset;
+        self.0.green.children().iter().enumerate().map(move |(index, child)| {
+            let start: usize = offset;
+            offset += child.length();
+            match child {
+                GreenElement::Node (green) => SyntaxElement::Node (SyntaxNode (Rc::new(NodeData {
+                    green: green.clone(),
+                    index,
+                    offset: start,
+                    parent: Some (self.clone()),
+                }))),
+                GreenElement::Token (green) => SyntaxElement::Token (SyntaxToken {
+                    green: green.clone(),
+                    index,
+                    offset: start,
+                    parent: self.clone(),
+                }),
+            }
+        })
+    }
+
+    /// This node and all the nodes within it, in preorder
+    pub fn descendants(&self) -> Vec<SyntaxNode> {
+        let mut descendants: Vec<SyntaxNode> = vec![self.clone()];
+        for child in self.children() { descendants.extend(child.descendants()) }
+        descendants
+    }
+
+    /// The green node that this node is a view of
+    pub fn green(&self) -> &Arc<GreenNode> { &self.0.green }
+
+    /// The position of this node among the children of its parent
+    pub fn index(&self) -> usize { self.0.index }
+
+    /// The kind of this node
+    pub fn kind(&self) -> &'static str { self.0.green.kind() }
+
+    /// The node that this node is a child of, if it is not the root
+    pub fn parent(&self) -> Option<&SyntaxNode> { self.0.parent.as_ref() }
+
+    /// The span of the text covered by this node
+    pub fn span(&self) -> Span { Span::new(self.0.offset, self.0.offset + self.0.green.length()) }
+
+    /// The text covered by this node, including trivia
+    pub fn text(&self) -> String {
+        let mut text: String = String::with_capacity(self.0.green.length());
+        self.0.green.write_text(&mut text);
+        text
+    }
+
+    /// All the tokens within this node, in order
+    pub fn tokens(&self) -> Vec<SyntaxToken> {
+        let mut tokens: Vec<SyntaxToken> = Vec::new();
+        for child in self.children_with_tokens() {
+            match child {
+                SyntaxElement::Node (node) => tokens.extend(node.tokens()),
+                SyntaxElement::Token (token) => tokens.push(token),
+            }
+        }
+        tokens
+    }
+
+    /// Writes this node and its descendants to a formatter, one element per line
+    fn write_tree(&self, formatter: &mut Formatter, depth: usize) -> FormatResult {
+        let span: Span = self.span();
+        writeln!(formatter, "{:indent$}{}@{}..{}", "", self.kind(), span.start, span.end,
+            indent = depth * 2)?;
+        for child in self.children_with_tokens() {
+            match child {
+                SyntaxElement::Node (node) => node.write_tree(formatter, depth + 1)?,
+                SyntaxElement::Token (token) => writeln!(
+                    formatter,
+                    "{:indent$}{token:?}",
+                    "",
+                    indent = (depth + 1) * 2,
+                )?,
+            }
+        }
+        Ok (())
+    }
+
+}
+
+impl Debug for SyntaxNode {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult { self.write_tree(formatter, 0) }
+}
+
+impl Display for SyntaxNode {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        formatter.write_str(&self.text())
+    }
+}
+
+impl PartialEq for SyntaxNode {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0.green, &other.0.green) && self.0.offset == other.0.offset
+    }
+}
+
+impl Eq for SyntaxNode {}
+
+
+/// A token of a concrete syntax tree that knows its position and its parent
+#[derive(Clone, Eq, PartialEq)]
+pub struct SyntaxToken {
+    /// The green token that this token is a view of
+    green: Arc<GreenToken>,
+    /// The position of this token among the children of its parent
+    index: usize,
+    /// The position where this token starts
+    offset: usize,
+    /// The node that this token is a child of
+    parent: SyntaxNode,
+}
+
+impl SyntaxToken {
+
+    /// The green token that this token is a view of
+    pub fn green(&self) -> &Arc<GreenToken> { &self.green }
+
+    /// The position of this token among the children of its parent
+    pub fn index(&self) -> usize { self.index }
+
+    /// Returns `true` if this token is trivia, like whitespace or a comment
+    pub fn is_trivia(&self) -> bool { self.green.is_trivia() }
+
+    /// The kind of this token
+    pub fn kind(&self) -> &'static str { self.green.kind() }
+
+    /// The node that this token is a child of
+    pub fn parent(&self) -> &SyntaxNode { &self.parent }
+
+    /// The span of the text covered by this token
+    pub fn span(&self) -> Span { Span::new(self.offset, self.offset + self.green.length()) }
+
+    /// The text covered by this token
+    pub fn text(&self) -> &str { self.green.text() }
+
+}
+
+impl Debug for SyntaxToken {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        let span: Span = self.span();
+        write!(formatter, "{}@{}..{} {:?}", self.kind(), span.start, span.end, self.text())
+    }
+}
+
+impl Display for SyntaxToken {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult { formatter.write_str(self.text()) }
+}
+
+
+/// A node or token of a concrete syntax tree
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SyntaxElement {
+    /// A node
+    Node (SyntaxNode),
+    /// A token
+    Token (SyntaxToken),
+}
+
+impl SyntaxElement {
+
+    /// The kind of this element
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SyntaxElement::Node (node) => node.kind(),
+            SyntaxElement::Token (token) => token.kind(),
+        }
+    }
+
+    /// The span of the text covered by this element
+    pub fn span(&self) -> Span {
+        match self {
+            SyntaxElement::Node (node) => node.span(),
+            SyntaxElement::Token (token) => token.span(),
+        }
+    }
+
+}
@@ -0,0 +1,301 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Input,
+    IterationMessage,
+    Parser,
+    PermutationMessage,
+    Rejected,
+    Span,
+    Verbose,
+};
+use std::{
+    collections::BTreeSet,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult,
+    },
+};
+
+/// The severity of a `Diagnostic`, from most to least severe
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// A problem that makes the input invalid
+    Error,
+    /// A problem that does not make the input invalid
+    Warning,
+    /// Additional information about the input
+    Note,
+    /// A suggestion for how to change the input
+    Help,
+}
+
+impl Severity {
+
+    /// All `Severity`s, from most to least severe
+    pub const ALL: [Severity; 4] = [
+        Severity::Error,
+        Severity::Warning,
+        Severity::Note,
+        Severity::Help,
+    ];
+
+    /// The name of this `Severity` as it appears in compiler output
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+            Severity::Help => "help",
+        }
+    }
+
+}
+
+impl Display for Severity {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult { formatter.write_str(self.name()) }
+}
+
+
+/// A standard message type describing something about a span of input
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Diagnostic {
+    /// The text of the `Diagnostic`
+    pub message: String,
+    /// Other spans that are relevant to the `Diagnostic`, each with its own text
+    pub related: Vec<(Span, String)>,
+    /// The severity of the `Diagnostic`
+    pub severity: Severity,
+    /// The span of input that the `Diagnostic` describes
+    pub span: Span,
+}
+
+impl Diagnostic {
+
+    /// Creates a new `Diagnostic`
+    pub fn new(severity: Severity, span: Span, message: impl Into<String>) -> Self {
+        Self { message: message.into(), related: Vec::new(), severity, span }
+    }
+
+    /// Creates a new `Diagnostic` with `Severity::Error`
+    pub fn error(span: Span, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, span, message)
+    }
+
+    /// Creates a new `Diagnostic` with `Severity::Help`
+    pub fn help(span: Span, message: impl Into<String>) -> Self {
+        Self::new(Severity::Help, span, message)
+    }
+
+    /// Creates a new `Diagnostic` with `Severity::Note`
+    pub fn note(span: Span, message: impl Into<String>) -> Self {
+        Self::new(Severity::Note, span, message)
+    }
+
+    /// Creates a new `Diagnostic` with `Severity::Warning`
+    pub fn warning(span: Span, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, span, message)
+    }
+
+    /// Adds a related span to this `Diagnostic`
+    pub fn with_related(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.related.push((span, message.into()));
+        self
+    }
+
+}
+
+impl Ord for Diagnostic {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.span.start, self.span.end, self.severity, &self.message, &self.related).cmp(&(
+            other.span.start,
+            other.span.end,
+            other.severity,
+            &other.message,
+            &other.related,
+        ))
+    }
+}
+
+impl PartialOrd for Diagnostic {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some (self.cmp(other)) }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        write!(
+            formatter,
+            "{} [{}..{}]: {}",
+            self.severity,
+            self.span.start,
+            self.span.end,
+            self.message,
+        )?;
+        for (span, message) in &self.related {
+            write!(formatter, "\n  [{}..{}]: {}", span.start, span.end, message)?;
+        }
+        Ok (())
+    }
+}
+
+impl From<IterationMessage> for Diagnostic {
+    fn from(message: IterationMessage) -> Self {
+        match message {
+            IterationMessage::Stalled { parser, position } => Diagnostic::warning(
+                Span::new(position, position),
+                format!("`{parser}` matched nothing, so iteration stopped")
+            ),
+            IterationMessage::TrailingSeparator { position } => Diagnostic::error(
+                Span::new(position, position),
+                "trailing separator is not allowed"
+            ),
+        }
+    }
+}
+
+impl From<PermutationMessage> for Diagnostic {
+    fn from(message: PermutationMessage) -> Self {
+        match message {
+            PermutationMessage::Duplicated { member, position } => Diagnostic::error(
+                Span::new(position, position),
+                format!("member {member} appears more than once")
+            ),
+            PermutationMessage::Missing { member, position } => Diagnostic::error(
+                Span::new(position, position),
+                format!("member {member} is missing")
+            ),
+        }
+    }
+}
+
+impl From<Rejected> for Diagnostic {
+    fn from(rejected: Rejected) -> Self {
+        Diagnostic::error(rejected.span, format!("expected {}", rejected.label))
+    }
+}
+
+
+/// A container of `Diagnostic`s that keeps them sorted by position without duplicates, and can
+/// limit how many of each `Severity` it stores
+#[derive(Clone, Debug, Default)]
+pub struct Diagnostics {
+    /// The stored `Diagnostic`s of each `Severity`, sorted by position
+    diagnostics: [BTreeSet<Diagnostic>; 4],
+    /// The maximum number of stored `Diagnostic`s for each `Severity`
+    limits: [Option<usize>; 4],
+    /// The `Diagnostic`s of each `Severity` that were discarded because of a limit, so that their
+    /// duplicates are not counted again
+    omitted: [BTreeSet<Diagnostic>; 4],
+}
+
+impl Diagnostics {
+
+    /// Creates a new empty `Diagnostics` container without limits
+    pub fn new() -> Self { Self::default() }
+
+    /// Limits the number of `Diagnostic`s of a `Severity` stored in this container, keeping those
+    /// that occur first in the input
+    pub fn with_limit(mut self, severity: Severity, limit: usize) -> Self {
+        self.limits[severity as usize] = Some (limit);
+        self
+    }
+
+    /// The number of stored `Diagnostic`s with a `Severity`
+    pub fn count(&self, severity: Severity) -> usize { self.diagnostics[severity as usize].len() }
+
+    /// Returns `true` if this container stores any `Diagnostic`s with `Severity::Error`
+    pub fn has_errors(&self) -> bool { self.count(Severity::Error) > 0 }
+
+    /// Returns `true` if this container stores no `Diagnostic`s
+    pub fn is_empty(&self) -> bool { self.diagnostics.iter().all(BTreeSet::is_empty) }
+
+    /// Iterates over the stored `Diagnostic`s in order of position
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        let mut diagnostics: Vec<&Diagnostic> = self.diagnostics.iter().flatten().collect();
+        diagnostics.sort();
+        diagnostics.into_iter()
+    }
+
+    /// The number of stored `Diagnostic`s
+    pub fn len(&self) -> usize { self.diagnostics.iter().map(BTreeSet::len).sum() }
+
+    /// The number of `Diagnostic`s with a `Severity` that were discarded because of a limit
+    pub fn omitted(&self, severity: Severity) -> usize { self.omitted[severity as usize].len() }
+
+    /// Stores a `Diagnostic` in position order unless it is a duplicate, discarding the last
+    /// `Diagnostic` of its `Severity` if that exceeds a limit
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        let severity: usize = diagnostic.severity as usize;
+        let stored: &mut BTreeSet<Diagnostic> = &mut self.diagnostics[severity];
+        if self.omitted[severity].contains(&diagnostic) || !stored.insert(diagnostic) { return }
+        if let Some (limit) = self.limits[severity] && stored.len() > limit
+            && let Some (last) = stored.pop_last() {
+            self.omitted[severity].insert(last);
+        }
+    }
+
+    /// Returns the stored `Diagnostic`s in order of position
+    pub fn into_vec(self) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = self.diagnostics.into_iter().flatten().collect();
+        diagnostics.sort();
+        diagnostics
+    }
+
+}
+
+impl<D> Extend<D> for Diagnostics
+where
+    D: Into<Diagnostic>,
+{
+    fn extend<T: IntoIterator<Item = D>>(&mut self, diagnostics: T) {
+        for diagnostic in diagnostics { self.push(diagnostic.into()) }
+    }
+}
+
+impl<D> FromIterator<D> for Diagnostics
+where
+    D: Into<Diagnostic>,
+{
+    fn from_iter<T: IntoIterator<Item = D>>(diagnostics: T) -> Self {
+        let mut container: Self = Self::new();
+        container.extend(diagnostics);
+        container
+    }
+}
+
+impl IntoIterator for Diagnostics {
+
+    type Item = Diagnostic;
+
+    type IntoIter = std::vec::IntoIter<Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter { self.into_vec().into_iter() }
+
+}
+
+
+impl Verbose {
+
+    /// Applies a parser in this mode, storing its messages in a `Diagnostics` container, which
+    /// sorts them by position and enforces its limits for each `Severity`
+    ///
+    /// Messages are converted into `Diagnostic`s as the parser finishes, since containers of this
+    /// mode store messages of any type.
+    pub fn diagnose<'a, O, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+        diagnostics: Diagnostics,
+    ) -> (Result<O, E>, Diagnostics)
+    where
+        I: Input<'a>,
+        M: Into<Diagnostic>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        let (result, messages): (Result<O, E>, Vec<M>) = parser.verbose(input);
+        let mut diagnostics: Diagnostics = diagnostics;
+        diagnostics.extend(messages);
+        (result, diagnostics)
+    }
+
+}
@@ -0,0 +1,319 @@
+// Copyright Rob Gage 2025
+
+mod railroad;
+
+use std::{
+    cell::RefCell,
+    fmt::Write,
+};
+
+/// The precedence of choices when writing EBNF
+const CHOICE: u8 = 0;
+
+/// The precedence of sequences when writing EBNF
+const SEQUENCE: u8 = 1;
+
+/// The precedence of grammars that never need grouping when writing EBNF
+const ATOM: u8 = 2;
+
+thread_local! {
+    /// The rules that are being described by `Grammar::rule` on this thread
+    static DESCRIBING: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A description of the input matched by a parser, which can be exported as EBNF or as a railroad
+/// diagram
+///
+/// Parsers describe themselves with `Parser::describe`, and parsers named with `labelled` become
+/// the rules of the exported grammar.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Grammar {
+    /// Alternatives that are tried in order
+    Choice (Vec<Grammar>),
+    /// Nothing, matching without consuming input
+    Empty,
+    /// The end of the input
+    End,
+    /// Input that must follow, without being consumed
+    Lookahead (Box<Grammar>),
+    /// Input that must not follow, matching without consuming input
+    Not (Box<Grammar>),
+    /// Input that may be absent
+    Optional (Box<Grammar>),
+    /// Input that appears exactly once each in any order
+    Permutation (Vec<Grammar>),
+    /// A rule of the grammar that is described where it encloses this reference, which is how
+    /// recursive rules refer to themselves
+    Reference (&'static str),
+    /// Input that is repeated, possibly with a separator in between each repetition
+    Repeated {
+        /// The repeated input
+        item: Box<Grammar>,
+        /// The maximum number of repetitions, if there is one
+        maximum: Option<usize>,
+        /// The minimum number of repetitions
+        minimum: usize,
+        /// The input in between each repetition, if there is any
+        separator: Option<Box<Grammar>>,
+    },
+    /// A named rule of the grammar
+    Rule (&'static str, Box<Grammar>),
+    /// Input that appears in order
+    Sequence (Vec<Grammar>),
+    /// Input described in words, like a number or an identifier
+    Special (&'static str),
+    /// Literal input
+    Terminal (String),
+    /// Input matched by a parser that cannot describe itself
+    Unknown,
+}
+
+impl Grammar {
+
+    /// Creates a `Grammar::Choice`, merging alternatives that are choices themselves
+    pub fn choice(alternatives: impl IntoIterator<Item = Grammar>) -> Self {
+        let mut merged: Vec<Grammar> = Vec::new();
+        for alternative in alternatives {
+            match alternative {
+                Grammar::Choice (nested) => merged.extend(nested),
+                alternative => merged.push(alternative),
+            }
+        }
+        if merged.len() == 1 { merged.remove(0) } else { Grammar::Choice (merged) }
+    }
+
+    /// Creates a `Grammar::Optional`
+    pub fn optional(grammar: Grammar) -> Self {
+        match grammar {
+            Grammar::Empty => Grammar::Empty,
+            grammar => Grammar::Optional (Box::new(grammar)),
+        }
+    }
+
+    /// Creates a `Grammar::Repeated`
+    pub fn repeated(
+        item: Grammar,
+        separator: Option<Grammar>,
+        minimum: usize,
+        maximum: Option<usize>,
+    ) -> Self {
+        Grammar::Repeated {
+            item: Box::new(item),
+            maximum,
+            minimum,
+            separator: separator.filter(|separator| *separator != Grammar::Empty).map(Box::new),
+        }
+    }
+
+    /// Creates a `Grammar::Rule` with a body that is described by a function, or a
+    /// `Grammar::Reference` if the rule is already being described, so recursive rules can
+    /// describe themselves
+    pub fn rule(name: &'static str, describe: impl FnOnce() -> Grammar) -> Self {
+        if DESCRIBING.with_borrow(|describing| describing.contains(&name)) {
+            return Grammar::Reference (name)
+        }
+        DESCRIBING.with_borrow_mut(|describing| describing.push(name));
+        let body: Grammar = describe();
+        DESCRIBING.with_borrow_mut(|describing| describing.pop());
+        Grammar::Rule (name, Box::new(body))
+    }
+
+    /// Creates a `Grammar::Sequence`, merging items that are sequences themselves and leaving out
+    /// empty items
+    pub fn sequence(items: impl IntoIterator<Item = Grammar>) -> Self {
+        let mut merged: Vec<Grammar> = Vec::new();
+        for item in items {
+            match item {
+                Grammar::Empty => {}
+                Grammar::Sequence (nested) => merged.extend(nested),
+                item => merged.push(item),
+            }
+        }
+        match merged.len() {
+            0 => Grammar::Empty,
+            1 => merged.remove(0),
+            _ => Grammar::Sequence (merged),
+        }
+    }
+
+    /// Creates a `Grammar::Terminal`
+    pub fn terminal(text: impl Into<String>) -> Self { Grammar::Terminal (text.into()) }
+
+    /// Returns the rules of this grammar in order of first appearance, starting with this grammar,
+    /// which is named `grammar` if it is not a rule
+    pub fn rules(&self) -> Vec<(&'static str, &Grammar)> {
+        let mut rules: Vec<(&'static str, &Grammar)> = vec![match self {
+            Grammar::Rule (name, body) => (*name, &**body),
+            grammar => ("grammar", grammar),
+        }];
+        let mut index: usize = 0;
+        while index < rules.len() {
+            let mut nested: Vec<(&'static str, &Grammar)> = Vec::new();
+            rules[index].1.collect_rules(&mut nested);
+            for rule in nested {
+                if rules.iter().all(|(name, _)| *name != rule.0) { rules.push(rule) }
+            }
+            index += 1;
+        }
+        rules
+    }
+
+    /// Collects the rules nested directly within this grammar, without their own nested rules
+    fn collect_rules<'g>(&'g self, rules: &mut Vec<(&'static str, &'g Grammar)>) {
+        match self {
+            Grammar::Choice (grammars)
+            | Grammar::Permutation (grammars)
+            | Grammar::Sequence (grammars) => for grammar in grammars {
+                grammar.collect_rules(rules)
+            },
+            Grammar::Lookahead (grammar)
+            | Grammar::Not (grammar)
+            | Grammar::Optional (grammar) => grammar.collect_rules(rules),
+            Grammar::Repeated { item, separator, .. } => {
+                item.collect_rules(rules);
+                if let Some (separator) = separator { separator.collect_rules(rules) }
+            }
+            Grammar::Rule (name, body) => rules.push((name, body)),
+            Grammar::Empty
+            | Grammar::End
+            | Grammar::Reference (_)
+            | Grammar::Special (_)
+            | Grammar::Terminal (_)
+            | Grammar::Unknown => {}
+        }
+    }
+
+    /// Exports this grammar as EBNF, with one line for each rule
+    pub fn to_ebnf(&self) -> String {
+        let mut ebnf: String = String::new();
+        for (name, body) in self.rules() {
+            ebnf.push_str(name);
+            ebnf.push_str(" = ");
+            body.write_ebnf(&mut ebnf, CHOICE);
+            ebnf.push_str(" ;\n");
+        }
+        ebnf
+    }
+
+    /// Exports this grammar as an SVG image with a railroad diagram for each rule
+    pub fn to_railroad_svg(&self) -> String { railroad::render(&self.rules()) }
+
+    /// Writes this grammar as an EBNF expression, grouping it if it binds more loosely than the
+    /// surrounding expression
+    fn write_ebnf(&self, ebnf: &mut String, precedence: u8) {
+        match self {
+            Grammar::Choice (alternatives)
+                => write_ebnf_list(ebnf, alternatives, " | ", CHOICE, precedence),
+            Grammar::Empty => ebnf.push_str("\"\""),
+            Grammar::End => ebnf.push_str("? end of input ?"),
+            Grammar::Lookahead (grammar) => write_ebnf_special(ebnf, "followed by", grammar),
+            Grammar::Not (grammar) => write_ebnf_special(ebnf, "not followed by", grammar),
+            Grammar::Optional (grammar) => {
+                ebnf.push_str("[ ");
+                grammar.write_ebnf(ebnf, CHOICE);
+                ebnf.push_str(" ]");
+            }
+            Grammar::Permutation (grammars) => write_ebnf_special(
+                ebnf,
+                "in any order",
+                &Grammar::Sequence (grammars.clone())
+            ),
+            Grammar::Repeated { item, maximum, minimum, separator } => match (minimum, maximum) {
+                (0, None) if separator.is_none() => {
+                    ebnf.push_str("{ ");
+                    item.write_ebnf(ebnf, CHOICE);
+                    ebnf.push_str(" }");
+                }
+                _ => expand_repetition(item, separator.as_deref(), *minimum, *maximum)
+                    .write_ebnf(ebnf, precedence),
+            },
+            Grammar::Reference (name) | Grammar::Rule (name, _) => ebnf.push_str(name),
+            Grammar::Sequence (items) => write_ebnf_list(ebnf, items, " , ", SEQUENCE, precedence),
+            Grammar::Special (description) => {
+                let _ = write!(ebnf, "? {description} ?");
+            }
+            // EBNF terminals cannot contain the quote that encloses them, so terminals with both
+            // quotes are split into a sequence of terminals with one of them each
+            Grammar::Terminal (text) if text.contains('"') && text.contains('\'') => {
+                let parts: Vec<Grammar> = split_quotes(text).map(Grammar::Terminal).collect();
+                write_ebnf_list(ebnf, &parts, " , ", SEQUENCE, precedence);
+            }
+            Grammar::Terminal (text) => if text.contains('"') {
+                let _ = write!(ebnf, "'{text}'");
+            } else {
+                let _ = write!(ebnf, "\"{text}\"");
+            },
+            Grammar::Unknown => ebnf.push_str("? unknown ?"),
+        }
+    }
+
+}
+
+/// Rewrites a repetition using only sequences, optional input, and unbounded repetition without a
+/// separator, which are all that EBNF can express
+fn expand_repetition(
+    item: &Grammar,
+    separator: Option<&Grammar>,
+    minimum: usize,
+    maximum: Option<usize>,
+) -> Grammar {
+    if maximum == Some (0) { return Grammar::Empty }
+    if minimum == 0 {
+        return Grammar::optional(expand_repetition(item, separator, 1, maximum))
+    }
+    // every repetition after the first is preceded by the separator
+    let subsequent: Grammar = match separator {
+        Some (separator) => Grammar::sequence([separator.clone(), item.clone()]),
+        None => item.clone(),
+    };
+    let mut items: Vec<Grammar> = vec![item.clone()];
+    items.extend((1..minimum).map(|_| subsequent.clone()));
+    match maximum {
+        Some (maximum) => items.extend(
+            (minimum..maximum).map(|_| Grammar::optional(subsequent.clone()))
+        ),
+        None => items.push(Grammar::repeated(subsequent, None, 0, None)),
+    }
+    Grammar::sequence(items)
+}
+
+/// Splits text into the longest parts that do not contain both `"` and `'`
+fn split_quotes(text: &str) -> impl Iterator<Item = String> {
+    let mut parts: Vec<String> = vec![String::new()];
+    for character in text.chars() {
+        let part: &String = parts.last().expect("there is always a part");
+        let quoted: bool = match character {
+            '"' => part.contains('\''),
+            '\'' => part.contains('"'),
+            _ => false,
+        };
+        if quoted { parts.push(String::new()) }
+        parts.last_mut().expect("there is always a part").push(character);
+    }
+    parts.into_iter()
+}
+
+/// Writes a list of grammars joined by an operator as EBNF, grouping them if they bind more loosely
+/// than the surrounding expression
+fn write_ebnf_list(
+    ebnf: &mut String,
+    grammars: &[Grammar],
+    operator: &str,
+    precedence: u8,
+    surrounding_precedence: u8,
+) {
+    let grouped: bool = surrounding_precedence > precedence;
+    if grouped { ebnf.push_str("( ") }
+    for (index, grammar) in grammars.iter().enumerate() {
+        if index > 0 { ebnf.push_str(operator) }
+        grammar.write_ebnf(ebnf, precedence + 1);
+    }
+    if grouped { ebnf.push_str(" )") }
+}
+
+/// Writes a grammar that EBNF cannot express as a special sequence describing it
+fn write_ebnf_special(ebnf: &mut String, description: &str, grammar: &Grammar) {
+    let _ = write!(ebnf, "? {description} ");
+    grammar.write_ebnf(ebnf, ATOM);
+    ebnf.push_str(" ?");
+}
@@ -0,0 +1,352 @@
+// Copyright Rob Gage 2025
+
+use crate::Grammar;
+use std::fmt::Write;
+
+/// The height of the boxes drawn for terminals and other leaves
+const BOX_HEIGHT: usize = 22;
+
+/// The horizontal space reserved for the rails on each side of a choice or repetition
+const RAIL: usize = 20;
+
+/// The space in between parts of a diagram
+const GAP: usize = 10;
+
+/// The height of a line of label text
+const LABEL_HEIGHT: usize = 14;
+
+/// The space around each diagram in the image
+const MARGIN: usize = 20;
+
+/// The styles used by the exported image
+const STYLE: &str = "\
+path { fill: none; stroke: #333; stroke-width: 1.5 } \
+rect { fill: #fff; stroke: #333; stroke-width: 1.5 } \
+rect.group { fill: none; stroke-dasharray: 4 3 } \
+text { font-family: monospace; font-size: 12px; text-anchor: middle } \
+text.label, text.rule { text-anchor: start } \
+text.rule { font-size: 14px; font-weight: bold } \
+text.special { font-style: italic }";
+
+/// The space that a diagram of a grammar takes up around the line that enters and leaves it
+#[derive(Clone, Copy)]
+struct Size {
+    /// The height below the line
+    down: usize,
+    /// The height above the line
+    up: usize,
+    /// The width
+    width: usize,
+}
+
+/// Renders a railroad diagram for each rule of a grammar as an SVG image
+pub(super) fn render(rules: &[(&'static str, &Grammar)]) -> String {
+    let mut body: String = String::new();
+    let mut height: usize = 0;
+    let mut width: usize = 0;
+    for (name, grammar) in rules {
+        let size: Size = measure(grammar);
+        let top: usize = height + MARGIN;
+        let line: usize = top + LABEL_HEIGHT + GAP + size.up;
+        let _ = write!(
+            body,
+            "<text class=\"rule\" x=\"{MARGIN}\" y=\"{}\">{}</text>",
+            top + LABEL_HEIGHT,
+            escape(name)
+        );
+        // the diagram starts and ends with short bars across its line
+        let end: usize = MARGIN + GAP + size.width + GAP;
+        let _ = write!(
+            body,
+            "<path d=\"M{MARGIN} {} v{BOX_HEIGHT} M{MARGIN} {line} h{GAP} M{} {line} h{GAP} \
+            M{end} {} v{BOX_HEIGHT}\"/>",
+            line - BOX_HEIGHT / 2,
+            MARGIN + GAP + size.width,
+            line - BOX_HEIGHT / 2,
+        );
+        draw(grammar, MARGIN + GAP, line, &mut body);
+        height = line + size.down;
+        width = width.max(end + MARGIN);
+    }
+    height += MARGIN;
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+        viewBox=\"0 0 {width} {height}\"><style>{STYLE}</style>{body}</svg>"
+    )
+}
+
+/// Escapes text for use in an SVG image
+fn escape(text: &str) -> String {
+    let mut escaped: String = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Returns the text and style class of the box drawn for a grammar, if it is drawn as a box
+fn leaf(grammar: &Grammar) -> Option<(String, &'static str)> {
+    match grammar {
+        Grammar::End => Some (("end of input".to_string(), "special")),
+        Grammar::Reference (name) | Grammar::Rule (name, _)
+            => Some ((name.to_string(), "rule-reference")),
+        Grammar::Special (description) => Some ((description.to_string(), "special")),
+        Grammar::Terminal (text) => Some ((text.clone(), "terminal")),
+        Grammar::Unknown => Some (("?".to_string(), "special")),
+        _ => None,
+    }
+}
+
+/// Returns the label drawn above a group of grammars, if a grammar is drawn as a group
+fn group(grammar: &Grammar) -> Option<(&'static str, Grammar)> {
+    match grammar {
+        Grammar::Lookahead (grammar) => Some (("followed by", (**grammar).clone())),
+        Grammar::Not (grammar) => Some (("not followed by", (**grammar).clone())),
+        Grammar::Permutation (grammars)
+            => Some (("in any order", Grammar::Sequence (grammars.clone()))),
+        _ => None,
+    }
+}
+
+/// Returns the label drawn below a repetition, if its number of repetitions is limited
+fn repetition_label(minimum: usize, maximum: Option<usize>) -> Option<String> {
+    match maximum {
+        Some (maximum) => Some (format!("{}..{maximum} times", minimum.max(1))),
+        None if minimum > 1 => Some (format!("at least {minimum} times")),
+        None => None,
+    }
+}
+
+/// Returns the width of a box containing text
+fn text_width(text: &str) -> usize { text.chars().count() * 8 + 2 * GAP }
+
+/// Measures the diagram of a grammar
+fn measure(grammar: &Grammar) -> Size {
+    if let Some ((text, _)) = leaf(grammar) {
+        return Size { down: BOX_HEIGHT / 2, up: BOX_HEIGHT / 2, width: text_width(&text) }
+    }
+    if let Some ((label, grammar)) = group(grammar) {
+        let inner: Size = measure(&grammar);
+        return Size {
+            down: inner.down + GAP,
+            up: inner.up + GAP + LABEL_HEIGHT,
+            width: (inner.width + 2 * GAP).max(text_width(label)),
+        }
+    }
+    match grammar {
+        Grammar::Choice (alternatives) => measure_choice(alternatives),
+        Grammar::Optional (grammar) => measure_choice(&[(**grammar).clone(), Grammar::Empty]),
+        Grammar::Repeated { item, maximum, minimum, separator } => {
+            let loop_size: Size = measure_loop(item, separator.as_deref(), *minimum, *maximum);
+            if *minimum == 0 {
+                measure_choice_sizes(&[loop_size, measure(&Grammar::Empty)])
+            } else { loop_size }
+        }
+        Grammar::Sequence (items) => items.iter().map(measure).fold(
+            Size { down: 0, up: 0, width: 0 },
+            |total, item| Size {
+                down: total.down.max(item.down),
+                up: total.up.max(item.up),
+                width: if total.width == 0 { item.width } else { total.width + GAP + item.width },
+            }
+        ),
+        _ => Size { down: 0, up: 0, width: 2 * GAP },
+    }
+}
+
+/// Measures the diagram of a choice between alternatives
+fn measure_choice(alternatives: &[Grammar]) -> Size {
+    measure_choice_sizes(&alternatives.iter().map(measure).collect::<Vec<Size>>())
+}
+
+/// Measures the diagram of a choice between alternatives with known sizes
+fn measure_choice_sizes(alternatives: &[Size]) -> Size {
+    let offsets: Vec<usize> = choice_offsets(alternatives);
+    let last: usize = alternatives.len() - 1;
+    Size {
+        down: offsets[last] + alternatives[last].down,
+        up: alternatives[0].up,
+        width: alternatives.iter().map(|size| size.width).max().unwrap_or(0) + 2 * RAIL,
+    }
+}
+
+/// Returns how far below the line each alternative of a choice is drawn
+fn choice_offsets(alternatives: &[Size]) -> Vec<usize> {
+    let mut offsets: Vec<usize> = vec![0];
+    for index in 1..alternatives.len() {
+        let previous: usize = offsets[index - 1] + alternatives[index - 1].down;
+        offsets.push(previous + GAP + alternatives[index].up);
+    }
+    offsets
+}
+
+/// Measures the diagram of a repetition that happens at least once
+fn measure_loop(
+    item: &Grammar,
+    separator: Option<&Grammar>,
+    minimum: usize,
+    maximum: Option<usize>,
+) -> Size {
+    let item: Size = measure(item);
+    let separator: Size = separator.map(measure).unwrap_or(Size { down: 0, up: 0, width: 0 });
+    let label: usize = if repetition_label(minimum, maximum).is_some() { LABEL_HEIGHT } else { 0 };
+    Size {
+        down: item.down + GAP + separator.up + separator.down + label,
+        up: item.up,
+        width: item.width.max(separator.width) + 2 * RAIL,
+    }
+}
+
+/// Draws the diagram of a grammar entering at a position on its line
+fn draw(grammar: &Grammar, x: usize, y: usize, svg: &mut String) {
+    let size: Size = measure(grammar);
+    if let Some ((text, class)) = leaf(grammar) {
+        let radius: usize = if class == "terminal" { BOX_HEIGHT / 2 } else { 0 };
+        let _ = write!(
+            svg,
+            "<rect x=\"{x}\" y=\"{}\" width=\"{}\" height=\"{BOX_HEIGHT}\" rx=\"{radius}\"/>\
+            <text class=\"{class}\" x=\"{}\" y=\"{}\">{}</text>",
+            y - BOX_HEIGHT / 2,
+            size.width,
+            x + size.width / 2,
+            y + 4,
+            escape(&text),
+        );
+        return
+    }
+    if let Some ((label, grammar)) = group(grammar) {
+        let inner: Size = measure(&grammar);
+        let top: usize = y - inner.up - GAP;
+        let _ = write!(
+            svg,
+            "<rect class=\"group\" x=\"{x}\" y=\"{top}\" width=\"{}\" height=\"{}\"/>\
+            <text class=\"label\" x=\"{x}\" y=\"{}\">{label}</text>\
+            <path d=\"M{x} {y} h{GAP} M{} {y} H{}\"/>",
+            size.width,
+            inner.up + inner.down + 2 * GAP,
+            top - 4,
+            x + GAP + inner.width,
+            x + size.width,
+        );
+        draw(&grammar, x + GAP, y, svg);
+        return
+    }
+    match grammar {
+        Grammar::Choice (alternatives) => draw_choice(alternatives, x, y, svg),
+        Grammar::Optional (grammar)
+            => draw_choice(&[(**grammar).clone(), Grammar::Empty], x, y, svg),
+        Grammar::Repeated { item, maximum, minimum, separator } => if *minimum == 0 {
+            let loop_size: Size = measure_loop(item, separator.as_deref(), *minimum, *maximum);
+            let sizes: [Size; 2] = [loop_size, measure(&Grammar::Empty)];
+            draw_rails(&sizes, x, y, svg);
+            draw_loop(item, separator.as_deref(), *minimum, *maximum, x + RAIL, y, svg);
+            let width: usize = sizes.iter().map(|size| size.width).max().unwrap_or(0);
+            let offset: usize = choice_offsets(&sizes)[1];
+            let _ = write!(svg, "<path d=\"M{} {} h{width}\"/>", x + RAIL, y + offset);
+        } else {
+            draw_loop(item, separator.as_deref(), *minimum, *maximum, x, y, svg)
+        },
+        Grammar::Sequence (items) => {
+            let mut cursor: usize = x;
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    let _ = write!(svg, "<path d=\"M{cursor} {y} h{GAP}\"/>");
+                    cursor += GAP;
+                }
+                draw(item, cursor, y, svg);
+                cursor += measure(item).width;
+            }
+        }
+        _ => {
+            let _ = write!(svg, "<path d=\"M{x} {y} h{}\"/>", size.width);
+        }
+    }
+}
+
+/// Draws the diagram of a choice between alternatives entering at a position on its line
+fn draw_choice(alternatives: &[Grammar], x: usize, y: usize, svg: &mut String) {
+    let sizes: Vec<Size> = alternatives.iter().map(measure).collect();
+    let offsets: Vec<usize> = choice_offsets(&sizes);
+    draw_rails(&sizes, x, y, svg);
+    let width: usize = sizes.iter().map(|size| size.width).max().unwrap_or(0);
+    for ((alternative, size), offset) in alternatives.iter().zip(&sizes).zip(&offsets) {
+        draw(alternative, x + RAIL, y + offset, svg);
+        // extend narrower alternatives to the right rail
+        if size.width < width {
+            let _ = write!(
+                svg,
+                "<path d=\"M{} {} H{}\"/>",
+                x + RAIL + size.width,
+                y + offset,
+                x + RAIL + width
+            );
+        }
+    }
+}
+
+/// Draws the rails that connect the alternatives of a choice to its line
+fn draw_rails(alternatives: &[Size], x: usize, y: usize, svg: &mut String) {
+    let width: usize = alternatives.iter().map(|size| size.width).max().unwrap_or(0);
+    let right: usize = x + RAIL + width;
+    for offset in choice_offsets(alternatives) {
+        let _ = write!(
+            svg,
+            "<path d=\"M{x} {y} h{GAP} V{} H{} M{right} {} h{GAP} V{y} H{}\"/>",
+            y + offset,
+            x + RAIL,
+            y + offset,
+            right + RAIL,
+        );
+    }
+}
+
+/// Draws the diagram of a repetition that happens at least once entering at a position on its line
+fn draw_loop(
+    item: &Grammar,
+    separator: Option<&Grammar>,
+    minimum: usize,
+    maximum: Option<usize>,
+    x: usize,
+    y: usize,
+    svg: &mut String,
+) {
+    let item_size: Size = measure(item);
+    let size: Size = measure_loop(item, separator, minimum, maximum);
+    let inner_width: usize = size.width - 2 * RAIL;
+    let right: usize = x + RAIL + inner_width;
+    let separator_size: Size = separator.map(measure).unwrap_or(Size { down: 0, up: 0, width: 0 });
+    let loop_line: usize = y + item_size.down + GAP + separator_size.up;
+    // the item is drawn on the line, centered in between the rails
+    let item_x: usize = x + RAIL + (inner_width - item_size.width) / 2;
+    let _ = write!(
+        svg,
+        "<path d=\"M{x} {y} H{item_x} M{} {y} H{}\"/>",
+        item_x + item_size.width,
+        right + RAIL,
+    );
+    draw(item, item_x, y, svg);
+    // the loop returns below the item, through the separator if there is one
+    let separator_x: usize = x + RAIL + (inner_width - separator_size.width) / 2;
+    let _ = write!(
+        svg,
+        "<path d=\"M{} {y} V{loop_line} H{} M{separator_x} {loop_line} H{} V{y}\"/>",
+        right + GAP,
+        separator_x + separator_size.width,
+        x + GAP,
+    );
+    if let Some (separator) = separator { draw(separator, separator_x, loop_line, svg) }
+    if let Some (label) = repetition_label(minimum, maximum) {
+        let _ = write!(
+            svg,
+            "<text class=\"label\" x=\"{}\" y=\"{}\">{label}</text>",
+            x + RAIL,
+            loop_line + separator_size.down + LABEL_HEIGHT,
+        );
+    }
+}
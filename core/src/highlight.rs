@@ -0,0 +1,217 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Annotation,
+    Input,
+    Mode,
+    modes::from_check,
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+    Parser,
+    Span,
+    SyntaxTag,
+};
+use std::cell::RefCell;
+
+thread_local! {
+    /// The highlights recorded by the innermost `Highlight::run` on this thread, if one is running
+    static HIGHLIGHTS: RefCell<Option<Vec<(Span, HighlightClass)>>> = const { RefCell::new(None) };
+}
+
+
+/// The class that input is highlighted with
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum HighlightClass {
+    /// A comment
+    Comment,
+    /// A name, like a variable or a function
+    Identifier,
+    /// A reserved word
+    Keyword,
+    /// A number literal
+    Number,
+    /// A string literal
+    String,
+}
+
+impl HighlightClass {
+
+    /// Every `HighlightClass`
+    pub const ALL: [HighlightClass; 5] = [
+        HighlightClass::Comment,
+        HighlightClass::Identifier,
+        HighlightClass::Keyword,
+        HighlightClass::Number,
+        HighlightClass::String,
+    ];
+
+    /// Returns the `HighlightClass` of a syntax token kind, if it is the kind of one
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|class| class.kind() == kind)
+    }
+
+    /// The kind of the syntax tokens with this class, named after the semantic token types of the
+    /// Language Server Protocol
+    pub const fn kind(self) -> &'static str {
+        match self {
+            HighlightClass::Comment => "comment",
+            HighlightClass::Identifier => "variable",
+            HighlightClass::Keyword => "keyword",
+            HighlightClass::Number => "number",
+            HighlightClass::String => "string",
+        }
+    }
+
+    /// The syntax tag of the parsers highlighted with this class, which are trivia if this is
+    /// `HighlightClass::Comment`
+    pub const fn tag(self) -> SyntaxTag {
+        match self {
+            HighlightClass::Comment => SyntaxTag::Trivia (self.kind()),
+            _ => SyntaxTag::Token (self.kind()),
+        }
+    }
+
+}
+
+
+/// Parser mode that only ensures the parser matches the input like `Check`, while recording the
+/// spans of the highlighted input
+///
+/// Highlights are collected with `Highlight::run`. Parsers tagged with a syntax token kind of a
+/// `HighlightClass`, usually with `highlight`, are highlighted when they succeed, and highlights
+/// from failed or rewound applications are left out, so recoverable parsers still highlight the
+/// valid parts of invalid input.
+pub struct Highlight;
+
+impl Highlight {
+
+    /// Applies a parser in this mode, returning the highlighted spans of the input in order
+    pub fn run<'a, O, E, M, I, P>(parser: &P, input: &'a I) -> Vec<(Span, HighlightClass)>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        // keep the highlights of an enclosing run separate so runs can be nested
+        let enclosing: Option<Vec<(Span, HighlightClass)>> = HIGHLIGHTS.replace(Some (Vec::new()));
+        let _: ModeResult<O, E, M, Self> = Self::apply_nested(parser, input);
+        HIGHLIGHTS.replace(enclosing).expect("highlights are recorded during the run")
+    }
+
+}
+
+/// Runs a function with the highlights recorded by the current `Highlight::run`, if there is one
+fn with_highlights(function: impl FnOnce(&mut Vec<(Span, HighlightClass)>)) {
+    HIGHLIGHTS.with_borrow_mut(|highlights| if let Some (highlights) = highlights {
+        function(highlights)
+    });
+}
+
+/// Returns the number of highlights recorded by the current `Highlight::run`
+fn highlight_count() -> usize {
+    HIGHLIGHTS.with_borrow(|highlights| highlights.as_ref().map_or(0, Vec::len))
+}
+
+
+impl Mode for Highlight {
+
+    type OutputForm<O> = ();
+
+    type ErrorForm<E> = ();
+
+    type MessageContainer<M> = ();
+
+    fn apply_parser<'a, O, E, M, I, P>(parser: &P, input: &'a I) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I> + ?Sized,
+    { from_check(parser.apply_highlight(input)) }
+
+    fn observes_nested() -> bool { true }
+
+    fn apply_nested<'a, O, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+    ) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        let start: usize = highlight_count();
+        let result: ModeResult<O, E, M, Self> = parser.apply::<Self>(input);
+        let end: usize = input.store_cursor();
+        // highlights from failed applications, or of input that was given back, are left out
+        with_highlights(|highlights| if !result.is_success()
+            || highlights.last().is_some_and(|(span, _)| span.end > end)
+        { highlights.truncate(start) });
+        result
+    }
+
+    fn apply_annotated<'a, O, E, M, I, P>(
+        annotation: Annotation,
+        parser: &P,
+        input: &'a I,
+    ) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        let Annotation::Syntax (tag) = annotation else { return Self::apply_nested(parser, input) };
+        let start: usize = input.store_cursor();
+        let mark: usize = highlight_count();
+        let result: ModeResult<O, E, M, Self> = Self::apply_nested(parser, input);
+        let end: usize = input.store_cursor();
+        let class: Option<HighlightClass> = match tag {
+            SyntaxTag::Node (_) => None,
+            SyntaxTag::Token (kind) | SyntaxTag::Trivia (kind) => HighlightClass::from_kind(kind),
+        };
+        if let Some (class) = class && result.is_success() {
+            // highlighted input is not highlighted again within
+            with_highlights(|highlights| {
+                highlights.truncate(mark);
+                if end > start { highlights.push((Span::new(start, end), class)) }
+            });
+        }
+        result
+    }
+
+    fn convert_output<O>(_: impl Into<O>) {}
+
+    fn convert_error<E>(_: impl Into<E>) {}
+
+    fn merge_outputs<OA1, OA2, OB>(_: (), _: (), _: impl Fn(OA1, OA2) -> OB) {}
+
+    fn merge_errors<EA1, EA2, EB>(_: (), _: (), _: impl Fn(EA1, EA2) -> EB) {}
+
+    fn merge_message_containers<M>(_: (), _: impl Into<()>) {}
+
+    fn map_output<OA, OB, E, M>(
+        result: ModeResult<OA, E, M, Self>,
+        _: impl Fn(OA) -> OB,
+    ) -> ModeResult<OB, E, M, Self> {
+        if result.is_success() { Success ((), ()) } else { Failure ((), ()) }
+    }
+
+    fn map_error<O, EA, EB, M>(
+        result: ModeResult<O, EA, M, Self>,
+        _: impl Fn(EA) -> EB,
+    ) -> ModeResult<O, EB, M, Self> {
+        if result.is_success() { Success ((), ()) } else { Failure ((), ()) }
+    }
+
+    fn map_messages<O, E, MA, MB>(
+        result: ModeResult<O, E, MA, Self>,
+        _: impl Fn(MA) -> MB,
+    ) -> ModeResult<O, E, MB, Self> {
+        if result.is_success() { Success ((), ()) } else { Failure ((), ()) }
+    }
+
+    fn new_message_container<M>() {}
+
+    fn add_message_to_container<M>(_: &mut (), _: M) {}
+
+    fn add_message_from_output<O, M>(_: &(), _: &mut (), _: impl FnOnce(&O) -> Option<M>) {}
+
+}
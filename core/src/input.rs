@@ -1,5 +1,7 @@
 // Copyright Rob Gage 2025
 
+use crate::MemoTable;
+
 /// Implementors represent input that can be consumed by parsers
 pub trait Input<'a> {
 
@@ -24,4 +26,7 @@ pub trait Input<'a> {
     /// Saves the position of the cursor of this `Input`
     fn store_cursor(&self) -> usize;
 
+    /// The table that memoized parsers remember their results in, if this `Input` keeps one
+    fn memo_table(&self) -> Option<&MemoTable> { None }
+
 }
\ No newline at end of file
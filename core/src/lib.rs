@@ -1,23 +1,73 @@
 // Copyright Rob Gage 2025
 
+mod annotation;
+mod backtracking;
 mod combinators;
+mod completion;
+mod cst;
+mod diagnostic;
+mod grammar;
+mod highlight;
 mod input;
 mod mode_result;
 mod modes;
 mod parsers;
+mod printer;
 mod macros;
+mod memo;
+mod node;
+mod span;
+mod trace;
 
 pub mod prelude {
 
     pub use crate::{
+        backtracking::Backtracking,
         combinators::Combinators,
+        completion::{
+            Completion,
+            Expected,
+        },
+        cst::{
+            Cst,
+            GreenElement,
+            GreenNode,
+            GreenToken,
+            SyntaxElement,
+            SyntaxNode,
+            SyntaxTag,
+            SyntaxToken,
+        },
+        diagnostic::{
+            Diagnostic,
+            Diagnostics,
+            Severity,
+        },
+        grammar::Grammar,
+        highlight::{
+            Highlight,
+            HighlightClass,
+        },
         mode_result::ModeResult,
+        node::{
+            IntoChildren,
+            Node,
+        },
         modes::{
             Check,
             Parse,
             Verbose,
         },
         parsers::*,
+        printer::{
+            Doc,
+            Printer,
+        },
+        span::Span,
+        trace::{
+            Trace,
+            TraceNode,
+        },
     };
 
     /// Shorthand result type for parsers
@@ -28,6 +78,8 @@ pub mod prelude {
 
 }
 
+pub use annotation::Annotation;
 pub use input::Input;
+pub use memo::MemoTable;
 pub use prelude::*;
 pub use modes::Mode;
\ No newline at end of file
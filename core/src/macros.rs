@@ -27,5 +27,39 @@ macro_rules! implement_modes {
                 ModeResult::Failure (error, messages) => (Err (error), messages),
             }
         }
+
+        fn apply_trace(
+            &self,
+            input: &$lifetime $I,
+        ) -> (Result<$O, $E>, Vec<$M>) {
+            match self.apply::<$crate::Trace>(input) {
+                ModeResult::Success (output, messages) => (Ok (output), messages),
+                ModeResult::Failure (error, messages) => (Err (error), messages),
+            }
+        }
+
+        fn apply_cst(
+            &self,
+            input: &$lifetime $I,
+        ) -> (Result<$O, $E>, Vec<$M>) {
+            match self.apply::<$crate::Cst>(input) {
+                ModeResult::Success (output, messages) => (Ok (output), messages),
+                ModeResult::Failure (error, messages) => (Err (error), messages),
+            }
+        }
+
+        fn apply_highlight(
+            &self,
+            input: &$lifetime $I,
+        ) -> bool {
+            self.apply::<$crate::Highlight>(input).is_success()
+        }
+
+        fn apply_completion(
+            &self,
+            input: &$lifetime $I,
+        ) -> bool {
+            self.apply::<$crate::Completion>(input).is_success()
+        }
     };
 }
\ No newline at end of file
@@ -0,0 +1,115 @@
+// Copyright Rob Gage 2025
+
+use crate::Span;
+use std::{
+    any::Any,
+    cell::{
+        Cell,
+        Ref,
+        RefCell,
+    },
+    collections::HashMap,
+};
+
+
+/// A remembered application of a memoized parser
+struct Entry {
+    /// The cursor position after the application
+    end: usize,
+    /// The position up to which the application looked at the input
+    examined: usize,
+    /// The result of the application, with its messages if they were kept
+    result: Box<dyn Any>,
+}
+
+
+/// Remembered results of memoized parsers, keyed by the parser's key and the cursor position where
+/// it was applied, which an `Input` can keep between parses to reuse results after it is edited
+///
+/// Inputs that keep a `MemoTable` must report every position they look at with
+/// `MemoTable::examine`, and call `MemoTable::edit` whenever they change, so results that depend on
+/// changed input are forgotten and results after the change are moved with it.
+#[derive(Default)]
+pub struct MemoTable {
+    /// The remembered results, keyed by memoized parser key and start position
+    entries: RefCell<HashMap<(&'static str, usize), Entry>>,
+    /// The furthest position looked at during the innermost memoized application
+    examined: Cell<usize>,
+}
+
+impl MemoTable {
+
+    /// Creates a new empty `MemoTable`
+    pub fn new() -> Self { Self::default() }
+
+    /// Forgets every remembered result
+    pub fn clear(&mut self) { self.entries.get_mut().clear() }
+
+    /// Adjusts the remembered results for input that is edited by replacing a span with a number
+    /// of new positions, forgetting results that looked at the span and moving results after it
+    pub fn edit(&mut self, span: Span, length: usize) {
+        let move_position = |position: usize| position - span.end + span.start + length;
+        let entries: &mut HashMap<(&'static str, usize), Entry> = self.entries.get_mut();
+        *entries = entries.drain()
+            .filter_map(|((key, start), entry)| if entry.examined <= span.start {
+                Some (((key, start), entry))
+            } else if start >= span.end {
+                let entry: Entry = Entry {
+                    end: move_position(entry.end),
+                    examined: move_position(entry.examined),
+                    result: entry.result,
+                };
+                Some (((key, move_position(start)), entry))
+            } else { None })
+            .collect();
+        self.examined.set(0);
+    }
+
+    /// Records that the input was looked at up to a position, exclusive
+    pub fn examine(&self, position: usize) {
+        if position > self.examined.get() { self.examined.set(position) }
+    }
+
+    /// Returns `true` if no results are remembered
+    pub fn is_empty(&self) -> bool { self.entries.borrow().is_empty() }
+
+    /// The number of remembered results
+    pub fn len(&self) -> usize { self.entries.borrow().len() }
+
+    /// Returns the end position and a copy of the result remembered for a memoized parser's key at
+    /// a position, if there is one
+    pub(crate) fn lookup<R>(&self, key: &'static str, start: usize) -> Option<(usize, R)>
+    where
+        R: Clone + 'static,
+    {
+        let entries: Ref<HashMap<(&'static str, usize), Entry>> = self.entries.borrow();
+        let entry: &Entry = entries.get(&(key, start))?;
+        let result: R = entry.result.downcast_ref::<R>()?.clone();
+        // the reused result looked at the same input as when it was remembered
+        self.examine(entry.examined);
+        Some ((entry.end, result))
+    }
+
+    /// Applies a function that consumes input from a position, remembering the result it gives to
+    /// remember, if any, for a memoized parser's key
+    pub(crate) fn remember<T, R>(
+        &self,
+        key: &'static str,
+        start: usize,
+        apply: impl FnOnce() -> (T, Option<R>, usize),
+    ) -> T
+    where
+        R: 'static,
+    {
+        let enclosing: usize = self.examined.replace(start);
+        let (applied, result, end): (T, Option<R>, usize) = apply();
+        let examined: usize = self.examined.get().max(end);
+        self.examined.set(enclosing.max(examined));
+        if let Some (result) = result {
+            let entry: Entry = Entry { end, examined, result: Box::new(result) };
+            self.entries.borrow_mut().insert((key, start), entry);
+        }
+        applied
+    }
+
+}
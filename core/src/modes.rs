@@ -1,6 +1,8 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Annotation,
+    Emitter,
     Input,
     ModeResult::{
         self,
@@ -12,6 +14,13 @@ use crate::{
 
 
 /// Implementors represent different modes that parsers run in, each accomplishing different goals
+///
+/// A custom `Mode` only has to describe its representational forms and how to combine them, then
+/// any parser can be run in it with `Parser::apply`. Parsers that cannot be applied generically,
+/// such as boxed `dyn Parser`s, are run through `Mode::apply_parser`, which by default applies
+/// them verbosely and converts the result into this mode's forms. The built-in modes apply them
+/// through entry points of their own instead, so they also observe the parsers nested in boxed
+/// parsers, but a custom mode cannot be passed through a `dyn Parser` and does not.
 pub trait Mode
 where
     Self: Sized,
@@ -26,14 +35,103 @@ where
     /// The representational form of stored messages used by this mode
     type MessageContainer<M>;
 
-    /// Applies a parser using this `Mode`
+    /// Applies a parser using this `Mode`, through only the entry points that every parser
+    /// provides, including boxed `dyn Parser`s
     fn apply_parser<'a, O, E, M, I, P>(
         parser: &P,
         input: &'a I,
     ) -> ModeResult<O, E, M, Self>
     where
         I: Input<'a>,
-        P: Parser<'a, O, E, M, I> + ?Sized;
+        P: Parser<'a, O, E, M, I> + ?Sized,
+    {
+        let (result, messages): (Result<O, E>, Vec<M>) = parser.verbose(input);
+        let mut message_container: Self::MessageContainer<M> = Self::new_message_container();
+        for message in messages { Self::add_message_to_container(&mut message_container, message) }
+        match result {
+            Ok (output) => Success (Self::convert_output(output), message_container),
+            Err (error) => Failure (Self::convert_error(error), message_container),
+        }
+    }
+
+    /// Returns `true` if this `Mode` observes the parsers nested within other parsers, so results
+    /// cannot be reused without applying the parsers that produced them again
+    ///
+    /// Modes that override `apply_nested` or `apply_annotated` to observe parsers should return
+    /// `true`, which keeps `memoized` parsers from reusing results in them.
+    fn observes_nested() -> bool { false }
+
+    /// Applies a parser nested within another parser using this `Mode`, allowing the mode to
+    /// observe every parser invocation
+    fn apply_nested<'a, O, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+    ) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    { parser.apply::<Self>(input) }
+
+    /// Applies a parser, then passes its output to a function that decides whether the parser
+    /// matches and can emit messages about the output, like the function of a `try_map` or
+    /// `validate` parser, restoring the cursor if the function fails
+    ///
+    /// The output is needed to make the decision even in modes that do not produce outputs, so by
+    /// default the parser is parsed first and then applied again in this mode, letting the mode
+    /// observe the parsers nested within it.
+    fn apply_validated<'a, OA, OB, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+        function: impl FnOnce(OA, &mut Emitter<M>) -> Result<OB, E>,
+    ) -> ModeResult<OB, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, OA, E, M, I>,
+    {
+        let start: usize = input.store_cursor();
+        let output: OA = match parser.parse(input) {
+            Ok (output) => output,
+            // applying the parser again in this mode lets the mode observe why it fails
+            Err (error) => return match Self::apply_nested(parser, input) {
+                Failure (error, messages) => Failure (error, messages),
+                Success (_, messages) => {
+                    input.move_cursor(start);
+                    Failure (Self::convert_error(error), messages)
+                }
+            },
+        };
+        let mut emitted: Vec<M> = Vec::new();
+        let decided: Result<OB, E> = function(output, &mut Emitter::new(&mut emitted));
+        input.move_cursor(start);
+        // the parser is applied again even if the function fails, so its messages are kept
+        let mut messages: Self::MessageContainer<M> = match Self::apply_nested(parser, input) {
+            Success (_, messages) => messages,
+            Failure (error, messages) => return Failure (error, messages),
+        };
+        for message in emitted { Self::add_message_to_container(&mut messages, message) }
+        match decided {
+            Ok (output) => Success (Self::convert_output(output), messages),
+            Err (error) => {
+                input.move_cursor(start);
+                Failure (Self::convert_error(error), messages)
+            }
+        }
+    }
+
+    /// Applies a parser that a combinator annotated with information this `Mode` can observe
+    ///
+    /// This is how modes observe information like syntax tags and trace names without a hook for
+    /// each kind of information. By default the annotation is ignored and the parser is applied as
+    /// a nested parser.
+    fn apply_annotated<'a, O, E, M, I, P>(
+        _annotation: Annotation,
+        parser: &P,
+        input: &'a I,
+    ) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    { Self::apply_nested(parser, input) }
 
     /// Converts an output to its representational form in this mode
     fn convert_output<O>(output: impl Into<O>) -> Self::OutputForm<O>;
@@ -85,6 +183,17 @@ where
     /// Adds a message to a `Self::MessageContainer`
     fn add_message_to_container<M>(container: &mut Self::MessageContainer<M>, message: M);
 
+    /// Adds a message derived from an output to a `Self::MessageContainer`, only calling the
+    /// function if this mode stores messages
+    ///
+    /// There is no default, since outputs are opaque to modes in general and a mode that ignored
+    /// this would silently drop the warnings of `warn_if` and similar combinators.
+    fn add_message_from_output<O, M>(
+        output: &Self::OutputForm<O>,
+        container: &mut Self::MessageContainer<M>,
+        function: impl FnOnce(&O) -> Option<M>,
+    );
+
 }
 
 
@@ -103,28 +212,46 @@ impl Mode for Check {
     where
         I: Input<'a, >,
         P: Parser<'a, O, E, M, I> + ?Sized,
-    { if parser.check(input) { Success ((), ()) } else { Failure ((), ()) } }
+    { from_check(parser.check(input)) }
+
+    fn apply_validated<'a, OA, OB, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+        function: impl FnOnce(OA, &mut Emitter<M>) -> Result<OB, E>,
+    ) -> ModeResult<OB, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, OA, E, M, I>,
+    {
+        // the output decides whether the parser matches, so it is parsed rather than checked
+        let start: usize = input.store_cursor();
+        let Ok (output) = parser.parse(input) else { return Failure ((), ()) };
+        if function(output, &mut Emitter::discarding()).is_ok() { Success ((), ()) } else {
+            input.move_cursor(start);
+            Failure ((), ())
+        }
+    }
 
-    fn convert_output<O>(_: impl Into<O>) -> () { () }
+    fn convert_output<O>(_: impl Into<O>) {}
 
-    fn convert_error<E>(_: impl Into<E> ) -> () { () }
+    fn convert_error<E>(_: impl Into<E> ) {}
 
     fn merge_outputs<OA1, OA2, OB>(
         _: (),
         _: (),
         _: impl Fn(OA1, OA2) -> OB
-    ) -> Self::OutputForm<OB> { () }
+    ) -> Self::OutputForm<OB> {}
 
     fn merge_errors<EA1, EA2, EB>(
         _: (),
         _: (),
         _: impl Fn(EA1, EA2) -> EB,
-    ) -> Self::ErrorForm<EB> { () }
+    ) -> Self::ErrorForm<EB> {}
 
     fn merge_message_containers<M>(
         _: Self::MessageContainer<M>,
         _: impl Into<Self::MessageContainer<M>>
-    ) { () }
+    ) {}
 
     fn map_output<OA, OB, E, M>(
         result: ModeResult<OA, E, M, Self>,
@@ -147,10 +274,16 @@ impl Mode for Check {
         if result.is_success() {Success((), ()) } else { Failure((), ()) }
     }
 
-    fn new_message_container<M>() -> () { () }
+    fn new_message_container<M>() {}
 
     fn add_message_to_container<M>(_: &mut Self::MessageContainer<M>, _: M) {}
 
+    fn add_message_from_output<O, M>(
+        _: &Self::OutputForm<O>,
+        _: &mut Self::MessageContainer<M>,
+        _: impl FnOnce(&O) -> Option<M>,
+    ) {}
+
 }
 
 
@@ -176,6 +309,16 @@ impl Mode for Parse {
         }
     }
 
+    fn apply_validated<'a, OA, OB, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+        function: impl FnOnce(OA, &mut Emitter<M>) -> Result<OB, E>,
+    ) -> ModeResult<OB, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, OA, E, M, I>,
+    { validate_output(parser, input, function) }
+
     fn convert_output<O>(output: impl Into<O>) -> O { output.into() }
 
     fn convert_error<E>(error: impl Into<E>) -> E  { error.into() }
@@ -195,7 +338,7 @@ impl Mode for Parse {
     fn merge_message_containers<M>(
         _: (),
         _: impl Into<()>
-    ) -> () { () }
+    ) {}
 
     fn map_output<OA, OB, E, M>(
         result: ModeResult<OA, E, M, Self>,
@@ -227,10 +370,16 @@ impl Mode for Parse {
         }
     }
 
-    fn new_message_container<M>() -> () { () }
+    fn new_message_container<M>() {}
 
     fn add_message_to_container<M>(_: &mut Self::MessageContainer<M>, _: M) { }
 
+    fn add_message_from_output<O, M>(
+        _: &Self::OutputForm<O>,
+        _: &mut Self::MessageContainer<M>,
+        _: impl FnOnce(&O) -> Option<M>,
+    ) { }
+
 }
 
 
@@ -249,13 +398,17 @@ impl Mode for Verbose {
     where
         I: Input<'a>,
         P: Parser<'a, O, E, M, I> + ?Sized,
-    {
-        let (result, messages): (Result<O, E>, Vec<M>) = parser.verbose(input);
-        match result {
-            Ok (output) => Success (output, messages),
-            Err (error) => Failure (error, messages)
-        }
-    }
+    { from_verbose(parser.verbose(input)) }
+
+    fn apply_validated<'a, OA, OB, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+        function: impl FnOnce(OA, &mut Emitter<M>) -> Result<OB, E>,
+    ) -> ModeResult<OB, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, OA, E, M, I>,
+    { validate_output(parser, input, function) }
 
     fn convert_output<O>(output: impl Into<O>) -> O { output.into() }
 
@@ -320,4 +473,64 @@ impl Mode for Verbose {
         container.push(message);
     }
 
-}
\ No newline at end of file
+    fn add_message_from_output<O, M>(
+        output: &O,
+        container: &mut Vec<M>,
+        function: impl FnOnce(&O) -> Option<M>,
+    ) {
+        if let Some (message) = function(output) { container.push(message) }
+    }
+
+}
+
+
+/// Converts whether a parser matches into a result of a mode without outputs, errors or messages,
+/// like `Check`
+pub(crate) fn from_check<O, E, M, _Mode>(matches: bool) -> ModeResult<O, E, M, _Mode>
+where
+    _Mode: Mode<OutputForm<O> = (), ErrorForm<E> = (), MessageContainer<M> = ()>,
+{ if matches { Success ((), ()) } else { Failure ((), ()) } }
+
+/// Converts the result of `Parser::verbose` into a result of a mode that keeps outputs, errors and
+/// messages as they are, like `Verbose`
+pub(crate) fn from_verbose<O, E, M, _Mode>(
+    (result, messages): (Result<O, E>, Vec<M>),
+) -> ModeResult<O, E, M, _Mode>
+where
+    _Mode: Mode<OutputForm<O> = O, ErrorForm<E> = E, MessageContainer<M> = Vec<M>>,
+{
+    match result {
+        Ok (output) => Success (output, messages),
+        Err (error) => Failure (error, messages),
+    }
+}
+
+/// Applies a validated parser once in a mode that keeps outputs and errors as they are, like
+/// `Parse` or `Verbose`, deciding whether it matches with the output of that application
+pub(crate) fn validate_output<'a, OA, OB, E, M, I, P, _Mode>(
+    parser: &P,
+    input: &'a I,
+    function: impl FnOnce(OA, &mut Emitter<M>) -> Result<OB, E>,
+) -> ModeResult<OB, E, M, _Mode>
+where
+    I: Input<'a>,
+    P: Parser<'a, OA, E, M, I>,
+    _Mode: Mode<OutputForm<OA> = OA, OutputForm<OB> = OB, ErrorForm<E> = E>,
+{
+    let start: usize = input.store_cursor();
+    let (output, mut messages): (OA, _Mode::MessageContainer<M>)
+        = match _Mode::apply_nested(parser, input) {
+            Success (output, messages) => (output, messages),
+            Failure (error, messages) => return Failure (error, messages),
+        };
+    let mut emitted: Vec<M> = Vec::new();
+    let decided: Result<OB, E> = function(output, &mut Emitter::new(&mut emitted));
+    for message in emitted { _Mode::add_message_to_container(&mut messages, message) }
+    match decided {
+        Ok (output) => Success (output, messages),
+        Err (error) => {
+            input.move_cursor(start);
+            Failure (error, messages)
+        }
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright Rob Gage 2025
+
+use crate::Span;
+use std::fmt::{
+    Debug,
+    Display,
+    Formatter,
+    Result as FormatResult,
+};
+
+
+/// A generic tree of the named parsers that matched some input, built with `node` and `leaf`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Node<S> {
+    /// The nodes of the named parsers that matched within this one, in order
+    pub children: Vec<Node<S>>,
+    /// The name of the parser
+    pub rule: &'static str,
+    /// The span of input that the parser matched
+    pub span: Span,
+    /// The input that the parser matched
+    pub text: S,
+}
+
+impl<S> Node<S> {
+
+    /// The number of nodes in this tree, including this one
+    pub fn size(&self) -> usize { 1 + self.children.iter().map(Node::size).sum::<usize>() }
+
+    /// Writes this tree as indented text to a formatter, one node per line
+    fn write_text(&self, formatter: &mut Formatter, depth: usize) -> FormatResult
+    where
+        S: Debug,
+    {
+        writeln!(
+            formatter,
+            "{:indent$}{} [{}..{}] {:?}",
+            "",
+            self.rule,
+            self.span.start,
+            self.span.end,
+            self.text,
+            indent = depth * 2,
+        )?;
+        for child in &self.children { child.write_text(formatter, depth + 1)? }
+        Ok (())
+    }
+
+}
+
+impl<S: Debug> Display for Node<S> {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult { self.write_text(formatter, 0) }
+}
+
+
+/// Implementors are parser outputs that may contain `Node`s, which become the children of the
+/// `Node` built from them by `node`
+///
+/// Sequences, choices, and repetitions of parsers that output `Node`s have outputs that implement
+/// this trait, as do the outputs of tokens, which contain no `Node`s.
+pub trait IntoChildren<S> {
+
+    /// Adds the `Node`s contained in this output to a list of children, in order
+    fn add_children(self, children: &mut Vec<Node<S>>);
+
+}
+
+impl<S> IntoChildren<S> for Node<S> {
+    fn add_children(self, children: &mut Vec<Node<S>>) { children.push(self) }
+}
+
+impl<S, T: IntoChildren<S>> IntoChildren<S> for Option<T> {
+    fn add_children(self, children: &mut Vec<Node<S>>) {
+        if let Some (output) = self { output.add_children(children) }
+    }
+}
+
+impl<S, T: IntoChildren<S>> IntoChildren<S> for Vec<T> {
+    fn add_children(self, children: &mut Vec<Node<S>>) {
+        for output in self { output.add_children(children) }
+    }
+}
+
+impl<S> IntoChildren<S> for &str {
+    fn add_children(self, _: &mut Vec<Node<S>>) {}
+}
+
+impl<S> IntoChildren<S> for char {
+    fn add_children(self, _: &mut Vec<Node<S>>) {}
+}
+
+/// Macro to implement `IntoChildren` for tuples of various sizes
+macro_rules! implement_into_children {
+    ( $( $output:ident )* ) => {
+        #[allow(non_snake_case)]
+        impl<S, $( $output: IntoChildren<S>, )*> IntoChildren<S> for ($( $output, )*) {
+            #[allow(unused_variables)]
+            fn add_children(self, children: &mut Vec<Node<S>>) {
+                let ($( $output, )*) = self;
+                $( $output.add_children(children); )*
+            }
+        }
+    };
+}
+
+implement_into_children!();
+implement_into_children!(O1);
+implement_into_children!(O1 O2);
+implement_into_children!(O1 O2 O3);
+implement_into_children!(O1 O2 O3 O4);
+implement_into_children!(O1 O2 O3 O4 O5);
+implement_into_children!(O1 O2 O3 O4 O5 O6);
+implement_into_children!(O1 O2 O3 O4 O5 O6 O7);
+implement_into_children!(O1 O2 O3 O4 O5 O6 O7 O8);
+implement_into_children!(O1 O2 O3 O4 O5 O6 O7 O8 O9);
+implement_into_children!(O1 O2 O3 O4 O5 O6 O7 O8 O9 O10);
+implement_into_children!(O1 O2 O3 O4 O5 O6 O7 O8 O9 O10 O11);
+implement_into_children!(O1 O2 O3 O4 O5 O6 O7 O8 O9 O10 O11 O12);
+implement_into_children!(O1 O2 O3 O4 O5 O6 O7 O8 O9 O10 O11 O12 O13);
+implement_into_children!(O1 O2 O3 O4 O5 O6 O7 O8 O9 O10 O11 O12 O13 O14);
+implement_into_children!(O1 O2 O3 O4 O5 O6 O7 O8 O9 O10 O11 O12 O13 O14 O15);
+implement_into_children!(O1 O2 O3 O4 O5 O6 O7 O8 O9 O10 O11 O12 O13 O14 O15 O16);
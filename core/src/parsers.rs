@@ -5,63 +5,152 @@ mod choice;
 mod end;
 mod emitting;
 mod first;
+mod folded;
+mod formatted;
+mod invertible;
 mod iterated;
+mod labelled;
+mod lookahead;
 mod mapped;
+mod memoized;
 mod nothing;
 mod optional;
+mod permutation;
 mod recoverable;
+mod reported;
+mod sequence;
 mod sequenced;
+mod tagged;
 mod mapped_messages;
 mod mapped_error;
+mod negated;
+mod noded;
+mod noted;
 mod traced;
+mod try_mapped;
+mod validated;
+mod warned;
+
+pub use choice::{
+    Choice,
+    DynChoice,
+    EmptyChoice,
+    ParserList,
+};
+pub use emitting::Emitting;
+pub use end::End;
+pub use first::First;
+pub use folded::Folded;
+pub use formatted::Formatted;
+pub use invertible::Invertible;
+pub use iterated::{
+    Collected,
+    IterationMessage,
+    Iterated,
+};
+pub use labelled::Labelled;
+pub use lookahead::Lookahead;
+pub use mapped::Mapped;
+pub use mapped_error::MappedError;
+pub use mapped_messages::MappedMessages;
+pub use memoized::Memoized;
+pub use negated::Negated;
+pub use noded::Noded;
+pub use noted::Noted;
+pub use nothing::Nothing;
+pub use optional::Optional;
+pub use permutation::{
+    Permutation,
+    PermutationMessage,
+};
+pub use recoverable::Recoverable;
+pub use reported::Reported;
+pub use sequence::Sequence;
+pub use sequenced::{
+    Preceded,
+    Sequenced,
+    Terminated,
+};
+pub use tagged::Tagged;
+pub use traced::Traced;
+pub use try_mapped::{
+    Rejected,
+    TryMapped,
+};
+pub use validated::{
+    Emitter,
+    Validated,
+};
+pub use warned::Warned;
 
-use choice::Choice;
-use emitting::Emitting;
-use end::End;
-use first::First;
-use iterated::Iterated;
-use mapped::Mapped;
-use mapped_error::MappedError;
-use mapped_messages::MappedMessages;
-use nothing::Nothing;
-use optional::Optional;
-use recoverable::Recoverable;
-use sequenced::Sequenced;
-use traced::Traced;
+pub(crate) use traced::report_traced;
 
 use crate::{
+    Grammar,
     Input,
     Mode,
-    Verbose,
     ModeResult,
-    Combinators,
 };
-use std::ops::Deref;
 
 pub use boxed::boxed;
-pub use choice::choice;
+pub use choice::{
+    choice,
+    choice_dyn,
+    choice_list,
+};
 pub use emitting::emitting;
 pub use end::end;
 pub use first::first;
+pub use folded::{
+    foldl,
+    foldr,
+};
+pub use formatted::formatted;
+pub use invertible::invertible;
 pub use iterated::{
     repeated,
     repeated_at_least,
     separated,
     separated_at_least,
 };
+pub use labelled::labelled;
+pub use lookahead::lookahead;
 pub use mapped::mapped;
 pub use mapped_error::mapped_error;
 pub use mapped_messages::mapped_messages;
+pub use memoized::memoized;
+pub use negated::not;
+pub use noded::{
+    leaf,
+    node,
+};
 pub use nothing::nothing;
 pub use optional::optional;
+pub use permutation::permutation;
 pub use recoverable::recoverable;
+pub use sequence::sequence;
 pub use sequenced::{
     delimited,
     preceded,
     sequenced,
     terminated,
 };
+pub use tagged::{
+    highlighted,
+    syntax_token,
+    trivia,
+};
 pub use traced::traced;
+pub use try_mapped::{
+    filter,
+    try_map,
+};
+pub use validated::validate;
+pub use warned::{
+    deprecated,
+    emit_message,
+    warn_if,
+};
 
 /// Implementors can be parsed from an input type
 pub trait Parser<'a, O, E, M, I>
@@ -86,6 +175,32 @@ where
     /// Parses input, returning a fully detailed result with messages
     fn verbose(&self, input: &'a I) -> (Result<O, E>, Vec<M>);
 
+    /// Applies this parser in `Trace` mode through an entry point that boxed `dyn Parser`s
+    /// provide, so the parsers nested in them are traced
+    ///
+    /// This and the entry points for the other built-in modes that observe nested parsers are
+    /// implemented by `implement_modes!`, and otherwise fall back to applying the parser without
+    /// observing the parsers nested in it.
+    #[doc(hidden)]
+    fn apply_trace(&self, input: &'a I) -> (Result<O, E>, Vec<M>) { self.verbose(input) }
+
+    /// Applies this parser in `Cst` mode through an entry point that boxed `dyn Parser`s provide
+    #[doc(hidden)]
+    fn apply_cst(&self, input: &'a I) -> (Result<O, E>, Vec<M>) { self.verbose(input) }
+
+    /// Applies this parser in `Highlight` mode through an entry point that boxed `dyn Parser`s
+    /// provide
+    #[doc(hidden)]
+    fn apply_highlight(&self, input: &'a I) -> bool { self.check(input) }
+
+    /// Applies this parser in `Completion` mode through an entry point that boxed `dyn Parser`s
+    /// provide
+    #[doc(hidden)]
+    fn apply_completion(&self, input: &'a I) -> bool { self.check(input) }
+
+    /// Describes the input that this parser matches
+    fn describe(&self) -> Grammar { Grammar::Unknown }
+
 }
 
 
@@ -111,7 +226,7 @@ where
 }
 
 // implementation of boxed parsers
-impl <'a, O, E, M, I> Parser<'a, O, E, M, I> for Box<dyn Parser<'a, O, E, M, I>>
+impl <'a, 'b, O, E, M, I> Parser<'a, O, E, M, I> for Box<dyn Parser<'a, O, E, M, I> + 'b>
 where
     I: Input<'a>
 {
@@ -126,4 +241,14 @@ where
 
     fn verbose(&self, input: &'a I) -> (Result<O, E>, Vec<M>) { (**self).verbose(input) }
 
+    fn apply_trace(&self, input: &'a I) -> (Result<O, E>, Vec<M>) { (**self).apply_trace(input) }
+
+    fn apply_cst(&self, input: &'a I) -> (Result<O, E>, Vec<M>) { (**self).apply_cst(input) }
+
+    fn apply_highlight(&self, input: &'a I) -> bool { (**self).apply_highlight(input) }
+
+    fn apply_completion(&self, input: &'a I) -> bool { (**self).apply_completion(input) }
+
+    fn describe(&self) -> Grammar { (**self).describe() }
+
 }
\ No newline at end of file
@@ -1,15 +1,29 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Doc,
+    Grammar,
+    Backtracking,
     implement_modes,
     Input,
     Mode,
+    Noted,
     ModeResult::{
         self,
         Failure,
         Success,
     },
     Parser,
+    Printer,
+};
+use std::{
+    error::Error,
+    fmt::{
+        Display,
+        Formatter,
+        Result as FormatResult,
+    },
+    ops::Deref,
 };
 
 
@@ -22,6 +36,57 @@ macro_rules! implement_choice {
 
 macro_rules! choice_tuple {
     ( $first:ident $(, $rest:ident)* ) => {
+        #[allow(non_snake_case, unused_mut)]
+        impl<$first, $( $rest, )*> Choice<($first, $( $rest, )*)> {
+            /// Applies the parsers in order until one succeeds, attaching the messages of those
+            /// that fail as notes created by a function if one is given
+            fn choose<'a, O, E, M, I, _Mode>(
+                &self,
+                input: &'a I,
+                note: Option<&dyn Fn(M) -> M>,
+            ) -> ModeResult<O, E, M, _Mode>
+            where
+                _Mode: Mode,
+                I: Input<'a>,
+                $first: Parser<'a, O, E, M, I>,
+                $( $rest: Parser<'a, O, E, M, I>, )*
+            {
+                let ($first, $( $rest, )*) = &self.parsers;
+                let backtracking: Backtracking = self.policy(note);
+                let mut abandoned: _Mode::MessageContainer<M> = _Mode::new_message_container();
+                let result: ModeResult<O, E, M, _Mode> = _Mode::apply_nested($first, input);
+                $(
+                    let result: ModeResult<O, E, M, _Mode> = match result {
+                        Success (_, _) => return backtracking.resolve(abandoned, result),
+                        Failure (_, messages) => {
+                            abandoned = backtracking.abandon::<_Mode, M>(abandoned, messages);
+                            _Mode::apply_nested($rest, input)
+                        }
+                    };
+                )*
+                backtracking.resolve_noted(abandoned, result, note)
+            }
+        }
+
+        impl<'a, O, E, M, I, F, $first, $( $rest, )*> Parser<'a, O, E, M, I>
+        for Noted<Choice<($first, $( $rest, )*)>, F>
+        where
+            F: Fn(M) -> M,
+            I: Input<'a>,
+            $first: Parser<'a, O, E, M, I>,
+            $( $rest: Parser<'a, O, E, M, I>, )*
+        {
+            fn apply<_Mode: Mode>(
+                &self,
+                input: &'a I
+            ) -> ModeResult<O, E, M, _Mode> { self.parser.choose(input, Some (&self.note)) }
+
+            implement_modes!('a, O, E, M, I);
+
+            fn describe(&self) -> Grammar { self.parser.describe() }
+        }
+
+        #[allow(non_snake_case, unused_mut)]
         impl<'a, O, E, M, I, $first, $( $rest, )*> Parser<'a, O, E, M, I>
         for Choice<($first, $( $rest, )*)>
         where
@@ -32,24 +97,62 @@ macro_rules! choice_tuple {
             fn apply<_Mode: Mode>(
                 &self,
                 input: &'a I
-            ) -> ModeResult<O, E, M, _Mode> {
-                let ($first, $( $rest, )*) = &self.0;
-                let mut result: ModeResult<O, E, M, _Mode> = _Mode::apply_parser($first, input);
-                $(
-                    result = match result {
-                        ModeResult::Success(_, _) => return result,
-                        ModeResult::Failure(_, _) => _Mode::apply_parser($rest, input),
-                    };
-                )*
-                result
-            }
+            ) -> ModeResult<O, E, M, _Mode> { self.choose(input, None) }
 
             implement_modes!('a, O, E, M, I);
+
+            fn describe(&self) -> Grammar {
+                let ($first, $( $rest, )*) = &self.parsers;
+                Grammar::choice([$first.describe(), $( $rest.describe(), )*])
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<O, $first, $( $rest, )*> Printer<O> for Choice<($first, $( $rest, )*)>
+        where
+            $first: Printer<O>,
+            $( $rest: Printer<O>, )*
+        {
+            fn print(&self, output: &O) -> Option<Doc> {
+                let ($first, $( $rest, )*) = &self.parsers;
+                $first.print(output)$( .or_else(|| $rest.print(output)) )*
+            }
+
+            fn print_ignored(&self) -> Option<Doc> {
+                let ($first, $( $rest, )*) = &self.parsers;
+                $first.print_ignored()$( .or_else(|| $rest.print_ignored()) )*
+            }
         }
     }
 }
 
-pub struct Choice<PL> (PL);
+pub struct Choice<PL> {
+    /// The policy for messages from parsers that fail before one succeeds
+    backtracking: Backtracking,
+    /// The parsers that are applied in order
+    parsers: PL,
+}
+
+impl<PL> Choice<PL> {
+
+    /// Sets the policy for messages from parsers that fail before one succeeds
+    pub const fn backtracking(mut self, backtracking: Backtracking) -> Self {
+        self.backtracking = backtracking;
+        self
+    }
+
+    /// Attaches the messages of parsers that fail before the last one to the error of this
+    /// `Choice` if it fails, as notes created by a function, and discards them otherwise
+    pub const fn attach_notes<F>(self, note: F) -> Noted<Self, F> {
+        Noted { note, parser: self }
+    }
+
+    /// The policy used for messages from parsers that fail before one succeeds
+    fn policy<M>(&self, note: Option<&dyn Fn(M) -> M>) -> Backtracking {
+        if note.is_some() { Backtracking::KeepOnFailure } else { self.backtracking }
+    }
+
+}
 
 implement_choice!(P1);
 implement_choice!(P1 P2);
@@ -69,11 +172,200 @@ implement_choice!(P1 P2 P3 P4 P5 P6 P7 P8 P9 P10 P11 P12 P13 P14);
 implement_choice!(P1 P2 P3 P4 P5 P6 P7 P8 P9 P10 P11 P12 P13 P14 P15);
 implement_choice!(P1 P2 P3 P4 P5 P6 P7 P8 P9 P10 P11 P12 P13 P14 P15 P16);
 
-/// Optionally applies a parser, converting a failure into `Option::None`
+/// A list of parsers sharing one type that is known to contain at least one parser
+pub struct ParserList<L> (L);
+
+/// The error returned when a `Choice` is built from an empty list of parsers
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EmptyChoice;
+
+impl Display for EmptyChoice {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        write!(formatter, "a choice requires at least one parser")
+    }
+}
+
+impl Error for EmptyChoice {}
+
+/// Applies parsers from a non-empty list in order until one succeeds, returning the last failure
+/// otherwise, and attaching the messages of parsers that fail as notes created by a function if
+/// one is given
+fn choose<'a, O, E, M, I, P, _Mode>(
+    backtracking: Backtracking,
+    first: &P,
+    rest: &[P],
+    input: &'a I,
+    note: Option<&dyn Fn(M) -> M>,
+) -> ModeResult<O, E, M, _Mode>
+where
+    _Mode: Mode,
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+    let mut abandoned: _Mode::MessageContainer<M> = _Mode::new_message_container();
+    let mut result: ModeResult<O, E, M, _Mode> = _Mode::apply_nested(first, input);
+    for parser in rest {
+        match result {
+            Success (_, _) => break,
+            Failure (_, messages) => {
+                abandoned = backtracking.abandon::<_Mode, M>(abandoned, messages);
+                result = _Mode::apply_nested(parser, input);
+            }
+        }
+    }
+    backtracking.resolve_noted(abandoned, result, note)
+}
+
+impl<'a, O, E, M, I, L, P> Parser<'a, O, E, M, I> for Choice<ParserList<L>>
+where
+    I: Input<'a>,
+    L: Deref<Target = [P]>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
+        let (first, rest): (&P, &[P]) = self.parsers.0.split_first()
+            .expect("parser lists are never empty");
+        choose(self.backtracking, first, rest, input, None)
+    }
+
+    implement_modes!('a, O, E, M, I);
+
+    fn describe(&self) -> Grammar {
+        Grammar::choice(self.parsers.0.iter().map(|parser| parser.describe()))
+    }
+
+}
+
+impl<'a, O, E, M, I, F, L, P> Parser<'a, O, E, M, I> for Noted<Choice<ParserList<L>>, F>
+where
+    F: Fn(M) -> M,
+    I: Input<'a>,
+    L: Deref<Target = [P]>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
+        let (first, rest): (&P, &[P]) = self.parser.parsers.0.split_first()
+            .expect("parser lists are never empty");
+        choose(self.parser.policy(Some (&self.note)), first, rest, input, Some (&self.note))
+    }
+
+    implement_modes!('a, O, E, M, I);
+
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
+}
+
+impl<O, L, P> Printer<O> for Choice<ParserList<L>>
+where
+    L: Deref<Target = [P]>,
+    P: Printer<O>,
+{
+
+    fn print(&self, output: &O) -> Option<Doc> {
+        self.parsers.0.iter().find_map(|parser| parser.print(output))
+    }
+
+    fn print_ignored(&self) -> Option<Doc> {
+        self.parsers.0.iter().find_map(|parser| parser.print_ignored())
+    }
+
+}
+
+impl<'a, O, E, M, I, P, const N: usize> Parser<'a, O, E, M, I> for Choice<[P; N]>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
+        const { assert!(N > 0, "a choice requires at least one parser") }
+        let (first, rest): (&P, &[P]) = self.parsers.split_first()
+            .expect("arrays of parsers are never empty");
+        choose(self.backtracking, first, rest, input, None)
+    }
+
+    implement_modes!('a, O, E, M, I);
+
+    fn describe(&self) -> Grammar {
+        Grammar::choice(self.parsers.iter().map(|parser| parser.describe()))
+    }
+
+}
+
+impl<'a, O, E, M, I, F, P, const N: usize> Parser<'a, O, E, M, I> for Noted<Choice<[P; N]>, F>
+where
+    F: Fn(M) -> M,
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
+        const { assert!(N > 0, "a choice requires at least one parser") }
+        let (first, rest): (&P, &[P]) = self.parser.parsers.split_first()
+            .expect("arrays of parsers are never empty");
+        choose(self.parser.policy(Some (&self.note)), first, rest, input, Some (&self.note))
+    }
+
+    implement_modes!('a, O, E, M, I);
+
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
+}
+
+impl<O, P, const N: usize> Printer<O> for Choice<[P; N]>
+where
+    P: Printer<O>,
+{
+
+    fn print(&self, output: &O) -> Option<Doc> {
+        self.parsers.iter().find_map(|parser| parser.print(output))
+    }
+
+    fn print_ignored(&self) -> Option<Doc> {
+        self.parsers.iter().find_map(|parser| parser.print_ignored())
+    }
+
+}
+
+/// Applies parsers in order until one succeeds, returning the failure of the last parser if none
+/// do
+///
+/// The parsers may be given as a tuple of up to 16 parsers, or as a non-empty array of parsers
+/// sharing one type. Use `choice_list` for lists whose length is only known at runtime. Messages
+/// from parsers that fail before one succeeds are discarded unless another `Backtracking` policy
+/// is set, or unless they are attached to the error as notes with `Choice::attach_notes`.
 pub const fn choice<'a, O, E, M, I, PL>(
     parser_list: PL,
-) -> impl Parser<'a, O, E, M, I>
+) -> Choice<PL>
 where
     I: Input<'a>,
     Choice<PL>: Parser<'a, O, E, M, I>,
-{ Choice (parser_list) }
\ No newline at end of file
+{ Choice { backtracking: Backtracking::Discard, parsers: parser_list } }
+
+/// Applies parsers from a `Vec` or slice in order until one succeeds, returning `EmptyChoice` if
+/// there are no parsers to choose from
+pub fn choice_list<'a, O, E, M, I, L, P>(
+    parsers: L,
+) -> Result<Choice<ParserList<L>>, EmptyChoice>
+where
+    I: Input<'a>,
+    L: Deref<Target = [P]>,
+    P: Parser<'a, O, E, M, I>,
+{
+    if parsers.is_empty() { return Err (EmptyChoice) }
+    Ok (Choice { backtracking: Backtracking::Discard, parsers: ParserList (parsers) })
+}
+
+/// A `Choice` between boxed parsers that are assembled at runtime
+pub type DynChoice<'a, O, E, M, I> = Choice<ParserList<Vec<Box<dyn Parser<'a, O, E, M, I> + 'a>>>>;
+
+/// Applies boxed parsers in order until one succeeds, allowing the alternatives to be assembled at
+/// runtime, and returning `EmptyChoice` if there are none
+pub fn choice_dyn<'a, O, E, M, I>(
+    parsers: Vec<Box<dyn Parser<'a, O, E, M, I> + 'a>>,
+) -> Result<DynChoice<'a, O, E, M, I>, EmptyChoice>
+where
+    I: Input<'a>,
+{ choice_list(parsers) }
@@ -1,6 +1,8 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Doc,
+    Grammar,
     implement_modes,
     Input,
     Mode,
@@ -9,7 +11,8 @@ use crate::{
         Success,
         Failure,
     },
-    Parser
+    Parser,
+    Printer,
 };
 use std::marker::PhantomData;
 
@@ -32,7 +35,7 @@ where
         &self,
         input: &'a I
     ) -> ModeResult<OB, E, M, _Mode> {
-        match self.parser.apply::<_Mode>(input) {
+        match _Mode::apply_nested(&self.parser, input) {
             Success (_, messages) => Success (_Mode::convert_output(self.output.clone()), messages),
             Failure (error, messages) => Failure (error, messages),
         }
@@ -40,14 +43,31 @@ where
 
     implement_modes!('a, OB, E, M, I);
 
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
+}
+
+impl<OA, OB, P> Printer<OB> for Emitting<OA, OB, P>
+where
+    OB: PartialEq,
+    P: Printer<OA>,
+{
+
+    fn print(&self, output: &OB) -> Option<Doc> {
+        if *output == self.output { self.parser.print_ignored() } else { None }
+    }
+
+    fn print_ignored(&self) -> Option<Doc> { self.parser.print_ignored() }
+
 }
 
 /// Replaces a parser's output
-pub const fn emitting<'a, OA, OB, E, M, I>(
-    parser: impl Parser<'a, OA, E, M, I>,
+pub const fn emitting<'a, OA, OB, E, M, I, P>(
+    parser: P,
     output: OB,
-) -> impl Parser<'a, OB, E, M, I>
+) -> Emitting<OA, OB, P>
 where
     I: Input<'a>,
     OB: Clone,
+    P: Parser<'a, OA, E, M, I>,
 { Emitting { output, parser, _phantom: PhantomData } }
\ No newline at end of file
@@ -1,6 +1,7 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Grammar,
     implement_modes,
     Input,
     Mode,
@@ -31,6 +32,8 @@ where
 
     implement_modes!('a, (), (), (), I);
 
+    fn describe(&self) -> Grammar { Grammar::End }
+
 }
 
 /// Matches the end of the provided input
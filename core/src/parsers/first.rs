@@ -1,6 +1,7 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Grammar,
     implement_modes,
     Input,
     Mode,
@@ -21,8 +22,8 @@ where
 {
 
     fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<Option<O>, E, M, _Mode> {
-        while let Some(_) = input.peek() {
-            match self.0.apply::<_Mode>(input) {
+        while input.peek().is_some() {
+            match _Mode::apply_nested(&self.0, input) {
                 Failure (_, _) => input.advance(),
                 success => return _Mode::map_output(success, |output| Some (output)),
             }
@@ -32,6 +33,14 @@ where
 
     implement_modes!('a, Option<O>, E, M, I);
 
+    fn describe(&self) -> Grammar {
+        let skipped: Grammar = Grammar::Special ("anything");
+        Grammar::sequence([
+            Grammar::repeated(skipped, None, 0, None),
+            Grammar::optional(self.0.describe()),
+        ])
+    }
+
 }
 
 /// Consumes input until a parser can be applied successfully or there is no input left
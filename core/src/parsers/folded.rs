@@ -0,0 +1,199 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Grammar,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+    Parser,
+    repeated,
+};
+use std::marker::PhantomData;
+
+pub struct Folded<O2, F, P1, P2> {
+    /// The function that combines a left operand, an operator, and a right operand into one operand
+    function: F,
+    /// The parser for operands
+    operand: P1,
+    /// The parser for the operators in between operands
+    operator: P2,
+    /// Whether operators associate to the right instead of the left
+    right_associative: bool,
+    _phantom: PhantomData<O2>,
+}
+
+impl<O2, F, P1, P2> Folded<O2, F, P1, P2> {
+
+    /// Folds the first operand and the operations that follow it from the left, combining each
+    /// operation with the operands before it as soon as it is parsed
+    fn fold_left<'a, O1, E, M, I, _Mode>(
+        &self,
+        input: &'a I,
+        first: _Mode::OutputForm<O1>,
+        mut message_container: _Mode::MessageContainer<M>,
+    ) -> ModeResult<O1, E, M, _Mode>
+    where
+        F: Fn(O1, O2, O1) -> O1,
+        I: Input<'a>,
+        P1: Parser<'a, O1, E, M, I>,
+        P2: Parser<'a, O2, E, M, I>,
+        _Mode: Mode,
+    {
+        let operation: Operation<'_, P1, P2>
+            = Operation { operand: &self.operand, operator: &self.operator };
+        let mut left: _Mode::OutputForm<O1> = first;
+        loop {
+            let cursor: usize = input.store_cursor();
+            match _Mode::apply_nested(&operation, input) {
+                Success (operation, messages) => {
+                    message_container
+                        = _Mode::merge_message_containers(message_container, messages);
+                    // operations are stopped like the items of `repeated` when they consume no
+                    // input
+                    if input.store_cursor() == cursor { return Success (left, message_container) }
+                    left = _Mode::merge_outputs(left, operation, |left, (operator, right)| {
+                        (self.function)(left, operator, right)
+                    });
+                }
+                Failure (_, messages) => return Success (
+                    left,
+                    _Mode::merge_message_containers(message_container, messages),
+                ),
+            }
+        }
+    }
+
+    /// Folds the first operand and the operations that follow it from the right into a single
+    /// output
+    fn fold_right<O1>(&self, first: O1, mut operations: Vec<(O2, O1)>) -> O1
+    where
+        F: Fn(O1, O2, O1) -> O1,
+    {
+        let Some ((mut operator, mut right)) = operations.pop() else { return first };
+        while let Some ((previous_operator, left)) = operations.pop() {
+            right = (self.function)(left, operator, right);
+            operator = previous_operator;
+        }
+        (self.function)(first, operator, right)
+    }
+
+}
+
+impl<'a, O1, O2, E, M, F, I, P1, P2> Parser<'a, O1, E, M, I> for Folded<O2, F, P1, P2>
+where
+    F: Fn(O1, O2, O1) -> O1,
+    I: Input<'a>,
+    P1: Parser<'a, O1, E, M, I>,
+    P2: Parser<'a, O2, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(
+        &self,
+        input: &'a I
+    ) -> ModeResult<O1, E, M, _Mode> {
+        let (first, message_container) = match _Mode::apply_nested(&self.operand, input) {
+            Success (output, messages) => (output, messages),
+            failure => return failure,
+        };
+        if !self.right_associative { return self.fold_left(input, first, message_container) }
+        // operations are iterated like any other parser, which stops when they consume no input
+        let operations = repeated(Operation { operand: &self.operand, operator: &self.operator });
+        match _Mode::apply_nested(&operations, input) {
+            Success (operations, messages) => Success (
+                _Mode::merge_outputs(first, operations, |first, operations| {
+                    self.fold_right(first, operations)
+                }),
+                _Mode::merge_message_containers(message_container, messages),
+            ),
+            Failure (error, messages) => Failure (
+                error,
+                _Mode::merge_message_containers(message_container, messages),
+            ),
+        }
+    }
+
+    implement_modes!('a, O1, E, M, I);
+
+    fn describe(&self) -> Grammar {
+        let operand: Grammar = self.operand.describe();
+        let operation: Grammar = Grammar::sequence([self.operator.describe(), operand.clone()]);
+        Grammar::sequence([operand, Grammar::repeated(operation, None, 0, None)])
+    }
+
+}
+
+/// An operator followed by an operand, borrowed from a `Folded` parser combinator
+struct Operation<'p, P1, P2> {
+    /// The parser for the operand
+    operand: &'p P1,
+    /// The parser for the operator
+    operator: &'p P2,
+}
+
+impl<'a, O1, O2, E, M, I, P1, P2> Parser<'a, (O2, O1), E, M, I> for Operation<'_, P1, P2>
+where
+    I: Input<'a>,
+    P1: Parser<'a, O1, E, M, I>,
+    P2: Parser<'a, O2, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<(O2, O1), E, M, _Mode> {
+        let cursor: usize = input.store_cursor();
+        let (operator, operator_messages) = match _Mode::apply_nested(self.operator, input) {
+            Success (output, messages) => (output, messages),
+            Failure (error, messages) => return Failure (error, messages),
+        };
+        match _Mode::apply_nested(self.operand, input) {
+            Success (operand, messages) => Success (
+                _Mode::merge_outputs(operator, operand, |operator, operand| (operator, operand)),
+                _Mode::merge_message_containers(operator_messages, messages),
+            ),
+            Failure (error, messages) => {
+                input.move_cursor(cursor);
+                Failure (error, _Mode::merge_message_containers(operator_messages, messages))
+            }
+        }
+    }
+
+    implement_modes!('a, (O2, O1), E, M, I);
+
+}
+
+/// Parses operands separated by operators, folding them from the left into a single output using a
+/// function that combines a left operand, an operator, and a right operand
+///
+/// Each operator and the operand after it are repeated like the items of `repeated`, so folding
+/// stops at an operation that consumes no input.
+pub const fn foldl<'a, O1, O2, E, M, F, I, P1, P2>(
+    operand: P1,
+    operator: P2,
+    function: F,
+) -> impl Parser<'a, O1, E, M, I>
+where
+    F: Fn(O1, O2, O1) -> O1,
+    I: Input<'a>,
+    P1: Parser<'a, O1, E, M, I>,
+    P2: Parser<'a, O2, E, M, I>,
+{ Folded { function, operand, operator, right_associative: false, _phantom: PhantomData } }
+
+/// Parses operands separated by operators, folding them from the right into a single output using a
+/// function that combines a left operand, an operator, and a right operand
+///
+/// Each operator and the operand after it are repeated like the items of `repeated`, so folding
+/// stops at an operation that consumes no input.
+pub const fn foldr<'a, O1, O2, E, M, F, I, P1, P2>(
+    operand: P1,
+    operator: P2,
+    function: F,
+) -> impl Parser<'a, O1, E, M, I>
+where
+    F: Fn(O1, O2, O1) -> O1,
+    I: Input<'a>,
+    P1: Parser<'a, O1, E, M, I>,
+    P2: Parser<'a, O2, E, M, I>,
+{ Folded { function, operand, operator, right_associative: true, _phantom: PhantomData } }
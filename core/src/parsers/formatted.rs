@@ -0,0 +1,57 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Doc,
+    Grammar,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult,
+    Parser,
+    Printer,
+};
+
+pub struct Formatted<P> {
+    /// The function that lays out the documents printed by the parser
+    format: fn(Doc) -> Doc,
+    /// The parser that is formatted
+    parser: P,
+}
+
+impl<'a, O, E, M, I, P> Parser<'a, O, E, M, I> for Formatted<P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
+        _Mode::apply_nested(&self.parser, input)
+    }
+
+    implement_modes!('a, O, E, M, I);
+
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
+}
+
+impl<O, P> Printer<O> for Formatted<P>
+where
+    P: Printer<O>,
+{
+
+    fn print(&self, output: &O) -> Option<Doc> { self.parser.print(output).map(self.format) }
+
+    fn print_ignored(&self) -> Option<Doc> { self.parser.print_ignored().map(self.format) }
+
+}
+
+/// Lays out the documents that a parser prints using a function, like `Doc::group`, without
+/// changing what it parses
+pub const fn formatted<'a, O, E, M, I, P>(
+    parser: P,
+    format: fn(Doc) -> Doc,
+) -> Formatted<P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{ Formatted { format, parser } }
@@ -0,0 +1,70 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Doc,
+    Grammar,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult,
+    Parser,
+    Printer,
+};
+use std::marker::PhantomData;
+
+pub struct Invertible<OA, OC, F, G, P> {
+    /// The function used to map the output of the parser
+    function: F,
+    /// The function used to map outputs back to outputs of the parser when printing
+    inverse: G,
+    /// The parser whose output is mapped
+    parser: P,
+    _phantom: PhantomData<(OA, OC)>,
+}
+
+impl<'a, OA, OB, OC, E, M, F, G, I, P> Parser<'a, OB, E, M, I>
+for Invertible<OA, OC, F, G, P>
+where
+    F: Fn(OA) -> OB,
+    I: Input<'a>,
+    P: Parser<'a, OA, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<OB, E, M, _Mode> {
+        _Mode::map_output(_Mode::apply_nested(&self.parser, input), &self.function)
+    }
+
+    implement_modes!('a, OB, E, M, I);
+
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
+}
+
+impl<OA, OB, OC, F, G, P> Printer<OB> for Invertible<OA, OC, F, G, P>
+where
+    G: Fn(&OB) -> Option<OC>,
+    P: Printer<OC>,
+{
+
+    fn print(&self, output: &OB) -> Option<Doc> { self.parser.print(&(self.inverse)(output)?) }
+
+    fn print_ignored(&self) -> Option<Doc> { self.parser.print_ignored() }
+
+}
+
+/// Maps a parser's output to another type using a function, and maps outputs back when printing
+/// using an inverse function that returns `None` for outputs that the function cannot produce
+///
+/// The inverse function may return any value that the parser can print, so parsers of borrowed
+/// slices of the input can print owned strings.
+pub const fn invertible<'a, OA, OB, OC, E, M, F, G, I, P>(
+    parser: P,
+    function: F,
+    inverse: G,
+) -> Invertible<OA, OC, F, G, P>
+where
+    F: Fn(OA) -> OB,
+    G: Fn(&OB) -> Option<OC>,
+    I: Input<'a>,
+    P: Parser<'a, OA, E, M, I> + Printer<OC>,
+{ Invertible { function, inverse, parser, _phantom: PhantomData } }
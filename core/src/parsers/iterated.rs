@@ -1,20 +1,68 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Doc,
+    Grammar,
     implement_modes,
     Input,
-    nothing,
     Mode,
     ModeResult::{
         self,
         Failure,
         Success
     },
-    Parser
+    Nothing,
+    Parser,
+    Printer,
+    Reported,
 };
-use std::marker::PhantomData;
+use std::{
+    any::type_name,
+    marker::PhantomData,
+};
+
+/// A message produced by an `Iterated` parser combinator
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IterationMessage {
+    /// An iteration succeeded without consuming any input, so iteration stopped to avoid looping
+    /// forever
+    Stalled {
+        /// The type name of the parser that did not consume any input
+        parser: &'static str,
+        /// The cursor position where the iteration stalled
+        position: usize,
+    },
+    /// A separator that is not allowed after the last item was found and left unconsumed
+    TrailingSeparator {
+        /// The cursor position of the trailing separator
+        position: usize,
+    },
+}
+
+impl From<IterationMessage> for () {
+    fn from(_: IterationMessage) -> Self {}
+}
+
+/// Policies for separators after the last item parsed by an `Iterated` parser combinator
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Trailing {
+    /// Trailing separators are left unconsumed and reported
+    Forbidden,
+    /// Trailing separators are consumed if present
+    Allowed,
+    /// Trailing separators must be present after at least one item
+    Required,
+}
 
-pub struct Iterated<O2, P1, P2> {
+pub struct Iterated<A, O1, O2, FA, FI, P1, P2> {
+    /// The function that accumulates each output of the iterated parser
+    accumulator: FA,
+    /// Whether to panic in debug builds when an iteration does not consume any input
+    asserts_progress: bool,
+    /// The function that creates the initial accumulated output
+    initial: FI,
+    /// Whether a separator may be applied before the first iteration
+    leading: bool,
     /// The maximum number of parser iterations that this combinator applies
     maximum: Option<usize>,
     /// The minimum number of parser iterations that this combinator applies
@@ -23,10 +71,40 @@ pub struct Iterated<O2, P1, P2> {
     parser: P1,
     /// The parser that is applied in between iterations
     separator: P2,
-    _phantom: PhantomData<O2>,
+    /// The policy for a separator after the last iteration
+    trailing: Trailing,
+    _phantom: PhantomData<(A, O1, O2)>,
 }
 
-impl<O2, P1, P2> Iterated<O2, P1, P2> {
+/// An `Iterated` parser combinator that accumulates its outputs using plain functions
+pub type Collected<A, O1, O2, P1, P2> = Iterated<A, O1, O2, fn(A, O1) -> A, fn() -> A, P1, P2>;
+
+impl<A, O1, O2, FA, FI, P1, P2> Iterated<A, O1, O2, FA, FI, P1, P2> {
+
+    /// Allows this iterated parser combinator to parse a separator before the first output
+    pub const fn allow_leading(mut self) -> Self {
+        self.leading = true;
+        self
+    }
+
+    /// Allows this iterated parser combinator to parse a separator after the last output
+    pub const fn allow_trailing(mut self) -> Self {
+        self.trailing = Trailing::Allowed;
+        self
+    }
+
+    /// Requires that this iterated parser combinator parse a separator after the last output
+    pub const fn require_trailing(mut self) -> Self {
+        self.trailing = Trailing::Required;
+        self
+    }
+
+    /// Panics in debug builds when an iteration of this iterated parser combinator succeeds without
+    /// consuming any input, instead of just stopping iteration
+    pub const fn assert_progress(mut self) -> Self {
+        self.asserts_progress = true;
+        self
+    }
 
     /// Requires that this iterated parser combinator parse at least a given number of outputs
     pub const fn at_least(mut self, minimum: usize) -> Self {
@@ -40,36 +118,92 @@ impl<O2, P1, P2> Iterated<O2, P1, P2> {
         self
     }
 
+    /// Reports separators that are not allowed after the last output and iterations that stop
+    /// because they consume no input as `IterationMessage`s
+    pub const fn reported(self) -> Reported<Self> { Reported (self) }
+
+    /// Collects the outputs of this iterated parser combinator into any extendable collection
+    pub fn collect_into<C>(self) -> Collected<C, O1, O2, P1, P2>
+    where
+        C: Default + Extend<O1>,
+    { self.fold(C::default, extend) }
+
+    /// Counts the outputs of this iterated parser combinator instead of storing them
+    pub fn count(self) -> Collected<usize, O1, O2, P1, P2> {
+        self.fold(|| 0, |count, _| count + 1)
+    }
+
+    /// Folds the outputs of this iterated parser combinator into an accumulator, using a function
+    /// to create the initial accumulator and another to accumulate each output
+    pub fn fold<B, GA, GI>(
+        self,
+        initial: GI,
+        accumulator: GA
+    ) -> Iterated<B, O1, O2, GA, GI, P1, P2>
+    where
+        GA: Fn(B, O1) -> B,
+        GI: Fn() -> B,
+    {
+        Iterated {
+            accumulator,
+            asserts_progress: self.asserts_progress,
+            initial,
+            leading: self.leading,
+            maximum: self.maximum,
+            minimum: self.minimum,
+            parser: self.parser,
+            separator: self.separator,
+            trailing: self.trailing,
+            _phantom: PhantomData,
+        }
+    }
+
 }
 
-impl<'a, O1, O2, E, M, I, P1, P2> Parser<'a, Vec<O1>, E, M, I> for Iterated<O2, P1, P2>
-where
-    I: Input<'a>,
-    P1: Parser<'a, O1, E, M, I>,
-    P2: Parser<'a, O2, E, M, I>,
-{
+impl<A, O1, O2, FA, FI, P1, P2> Iterated<A, O1, O2, FA, FI, P1, P2> {
 
-    fn apply<_Mode: Mode>(
+    /// Applies this iterated parser combinator, reporting how iteration stopped with a function if
+    /// one is given
+    fn iterate<'a, E, M, I, _Mode>(
         &self,
-        input: &'a I
-    ) -> ModeResult<Vec<O1>, E, M, _Mode> {
+        input: &'a I,
+        report: Option<fn(IterationMessage) -> M>,
+    ) -> ModeResult<A, E, M, _Mode>
+    where
+        FA: Fn(A, O1) -> A,
+        FI: Fn() -> A,
+        I: Input<'a>,
+        _Mode: Mode,
+        P1: Parser<'a, O1, E, M, I>,
+        P2: Parser<'a, O2, E, M, I>,
+    {
         let start_cursor: usize = input.store_cursor();
         let maximum: usize = if let Some (maximum) = self.maximum { maximum } else { usize::MAX };
-        let mut outputs: _Mode::OutputForm<Vec<O1>> = _Mode::convert_output(Vec::new());
+        let mut outputs: _Mode::OutputForm<A> = _Mode::convert_output((self.initial)());
         let mut output_count: usize = 0;
         let mut message_container: _Mode::MessageContainer<M> = _Mode::new_message_container();
+        // parse leading separator (if allowed)
+        if self.leading {
+            match _Mode::apply_nested(&self.separator, input) {
+                Success (_, messages) | Failure (_, messages) => message_container
+                    = _Mode::merge_message_containers(message_container, messages),
+            }
+        }
         loop {
             let cursor_before_separator: usize = input.store_cursor();
             let expects_item: bool = output_count < self.minimum;
             // parse separator (if necessary)
             if output_count > 0 {
-                match self.separator.apply::<_Mode>(input) {
+                if output_count == maximum && self.trailing == Trailing::Forbidden {
+                    return Success (outputs, message_container)
+                }
+                match _Mode::apply_nested(&self.separator, input) {
                     Success (_, messages) => message_container
                         = _Mode::merge_message_containers(message_container, messages),
                     Failure (error, messages) => {
                         message_container
                             = _Mode::merge_message_containers(message_container, messages);
-                        return if expects_item {
+                        return if expects_item || self.trailing == Trailing::Required {
                             input.move_cursor(start_cursor);
                             Failure (error, message_container)
                         } else {
@@ -78,66 +212,207 @@ where
                         }
                     },
                 }
+                if output_count == maximum { return Success (outputs, message_container) }
             }
+            let cursor_after_separator: usize = input.store_cursor();
             // parse item
-            match self.parser.apply::<_Mode>(input) {
+            match _Mode::apply_nested(&self.parser, input) {
                 Success (output, messages) => {
                     message_container
                         = _Mode::merge_message_containers(message_container, messages);
-                    outputs = _Mode::merge_outputs(outputs, output,|mut outputs, output| {
-                        outputs.push(output); outputs
-                    });
+                    // stop iterating once the required items are parsed if no input was consumed
+                    if !expects_item && input.store_cursor() == cursor_before_separator {
+                        let parser: &'static str = type_name::<P1>();
+                        if cfg!(debug_assertions) && self.asserts_progress {
+                            panic!(
+                                "iterated parser `{parser}` succeeded without consuming input at \
+                                position {cursor_before_separator}, so it would repeat forever"
+                            )
+                        }
+                        if let Some (report) = report {
+                            _Mode::add_message_to_container(
+                                &mut message_container,
+                                report(IterationMessage::Stalled {
+                                    parser,
+                                    position: cursor_before_separator
+                                })
+                            );
+                        }
+                        return Success (outputs, message_container)
+                    }
+                    outputs = _Mode::merge_outputs(outputs, output, &self.accumulator);
                     output_count += 1;
-                    if output_count == maximum { return Success (outputs, message_container) }
                 }
                 Failure (error, messages) => {
                     message_container
                         = _Mode::merge_message_containers(message_container, messages);
-                    return if expects_item {
+                    if expects_item {
+                        input.move_cursor(start_cursor);
+                        return Failure (error, message_container)
+                    }
+                    if output_count == 0 {
                         input.move_cursor(start_cursor);
-                        Failure (error, message_container)
-                    } else {
+                    } else if self.trailing == Trailing::Forbidden {
+                        // separators that match nothing, like those of `repeated`, are not reported
+                        if let Some (report) = report
+                            && cursor_after_separator > cursor_before_separator {
+                            _Mode::add_message_to_container(
+                                &mut message_container,
+                                report(IterationMessage::TrailingSeparator {
+                                    position: cursor_before_separator
+                                })
+                            );
+                        }
                         input.move_cursor(cursor_before_separator);
-                        Success (outputs, message_container)
                     }
+                    return Success (outputs, message_container)
                 }
             }
         }
     }
 
-    implement_modes!('a, Vec<O1>, E, M, I);
+}
 
+impl<'a, A, O1, O2, E, M, FA, FI, I, P1, P2> Parser<'a, A, E, M, I>
+for Iterated<A, O1, O2, FA, FI, P1, P2>
+where
+    FA: Fn(A, O1) -> A,
+    FI: Fn() -> A,
+    I: Input<'a>,
+    P1: Parser<'a, O1, E, M, I>,
+    P2: Parser<'a, O2, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<A, E, M, _Mode> {
+        self.iterate(input, None)
+    }
+
+    implement_modes!('a, A, E, M, I);
+
+    fn describe(&self) -> Grammar {
+        let separator: Grammar = self.separator.describe();
+        let leading: Grammar = if self.leading { Grammar::optional(separator.clone()) } else {
+            Grammar::Empty
+        };
+        let trailing: Grammar = match self.trailing {
+            Trailing::Forbidden => Grammar::Empty,
+            Trailing::Allowed => Grammar::optional(separator.clone()),
+            Trailing::Required => separator.clone(),
+        };
+        let items: Grammar = Grammar::repeated(
+            self.parser.describe(),
+            Some (separator),
+            self.minimum,
+            self.maximum
+        );
+        Grammar::sequence([leading, items, trailing])
+    }
+
+}
+
+impl<'a, A, O1, O2, E, M, FA, FI, I, P1, P2> Parser<'a, A, E, M, I>
+for Reported<Iterated<A, O1, O2, FA, FI, P1, P2>>
+where
+    FA: Fn(A, O1) -> A,
+    FI: Fn() -> A,
+    I: Input<'a>,
+    M: From<IterationMessage>,
+    P1: Parser<'a, O1, E, M, I>,
+    P2: Parser<'a, O2, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<A, E, M, _Mode> {
+        self.0.iterate(input, Some (M::from))
+    }
+
+    implement_modes!('a, A, E, M, I);
+
+    fn describe(&self) -> Grammar { self.0.describe() }
+
+}
+
+impl<O1, O2, FA, FI, P1, P2> Printer<Vec<O1>> for Iterated<Vec<O1>, O1, O2, FA, FI, P1, P2>
+where
+    P1: Printer<O1>,
+    P2: Printer<O2>,
+{
+
+    fn print(&self, items: &Vec<O1>) -> Option<Doc> {
+        if items.len() < self.minimum || self.maximum.is_some_and(|maximum| items.len() > maximum) {
+            return None
+        }
+        let mut docs: Vec<Doc> = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 { docs.push(self.separator.print_ignored()?) }
+            docs.push(self.parser.print(item)?);
+        }
+        if self.trailing == Trailing::Required && !items.is_empty() {
+            docs.push(self.separator.print_ignored()?);
+        }
+        Some (Doc::concat(docs))
+    }
+
+    fn print_ignored(&self) -> Option<Doc> { (self.minimum == 0).then_some(Doc::Nil) }
+
+}
+
+/// Extends a collection with a single item
+fn extend<C, O>(mut collection: C, item: O) -> C
+where
+    C: Extend<O>,
+{
+    collection.extend(Some (item));
+    collection
+}
+
+/// Pushes an item onto the end of a `Vec`
+fn push<O>(mut items: Vec<O>, item: O) -> Vec<O> {
+    items.push(item);
+    items
 }
 
 /// Iterates application of a parser
 pub const fn repeated<'a, O, E, M, I, P>(
     parser: P,
-) -> impl Parser<'a, Vec<O>, E, M, I>
+) -> Collected<Vec<O>, O, (), P, Nothing<E, M>>
 where
     I: Input<'a>,
     P: Parser<'a, O, E, M, I>,
-{ Iterated { maximum: None, minimum: 0, parser, separator: nothing(), _phantom: PhantomData } }
+{ separated(parser, Nothing (PhantomData)) }
 
 /// Iterates application of a parser at least a minimum number of times
 pub const fn repeated_at_least<'a, O, E, M, I, P>(
     parser: P,
     minimum: usize
-) -> impl Parser<'a, Vec<O>, E, M, I>
+) -> Collected<Vec<O>, O, (), P, Nothing<E, M>>
 where
     I: Input<'a>,
     P: Parser<'a, O, E, M, I>,
-{ Iterated { maximum: None, minimum, parser, separator: nothing(), _phantom: PhantomData } }
+{ repeated(parser).at_least(minimum) }
 
 /// Iterates application of a parser separated by application of another parser
 pub const fn separated<'a, E, I, M, O1, O2, P1, P2>(
     parser: P1,
     separator: P2
-) -> impl Parser<'a, Vec<O1>, E, M, I>
+) -> Collected<Vec<O1>, O1, O2, P1, P2>
 where
     I: Input<'a>,
     P1: Parser<'a, O1, E, M, I>,
     P2: Parser<'a, O2, E, M, I>,
-{ Iterated { maximum: None, minimum: 0, parser, separator, _phantom: PhantomData } }
+{
+    Iterated {
+        accumulator: push,
+        asserts_progress: false,
+        initial: Vec::new,
+        leading: false,
+        maximum: None,
+        minimum: 0,
+        parser,
+        separator,
+        trailing: Trailing::Forbidden,
+        _phantom: PhantomData,
+    }
+}
 
 /// Iterates application of a parser separated by application of another parser at a minimum number
 /// of times
@@ -145,10 +420,9 @@ pub const fn separated_at_least<'a, E, I, M, O1, O2, P1, P2>(
     parser: P1,
     separator: P2,
     minimum: usize
-) -> impl Parser<'a, Vec<O1>, E, M, I>
+) -> Collected<Vec<O1>, O1, O2, P1, P2>
 where
     I: Input<'a>,
     P1: Parser<'a, O1, E, M, I>,
     P2: Parser<'a, O2, E, M, I>,
-{ Iterated { maximum: None, minimum, parser, separator, _phantom: PhantomData } }
-
+{ separated(parser, separator).at_least(minimum) }
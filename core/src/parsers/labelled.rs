@@ -0,0 +1,64 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Annotation,
+    Doc,
+    Grammar,
+    SyntaxTag,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult,
+    Parser,
+    parsers::report_traced,
+    Printer,
+};
+
+pub struct Labelled<P> {
+    /// The name of the grammar rule that the parser represents
+    name: &'static str,
+    /// The parser that is labelled
+    parser: P,
+}
+
+impl<'a, O, E, M, I, P> Parser<'a, O, E, M, I> for Labelled<P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
+        let annotation: Annotation = Annotation::Syntax (SyntaxTag::Node (self.name));
+        report_traced(self.name, input, || _Mode::apply_annotated(annotation, &self.parser, input))
+    }
+
+    implement_modes!('a, O, E, M, I);
+
+    fn describe(&self) -> Grammar {
+        Grammar::Rule (self.name, Box::new(self.parser.describe()))
+    }
+
+}
+
+impl<O, P> Printer<O> for Labelled<P>
+where
+    P: Printer<O>,
+{
+
+    fn print(&self, output: &O) -> Option<Doc> { self.parser.print(output) }
+
+    fn print_ignored(&self) -> Option<Doc> { self.parser.print_ignored() }
+
+}
+
+/// Names a parser, making it a rule of the grammar that describes the parsers it is part of and a
+/// node of concrete syntax trees, which is applied within a `tracing` span if the `tracing`
+/// feature is enabled
+pub const fn labelled<'a, O, E, M, I, P>(
+    parser: P,
+    name: &'static str,
+) -> Labelled<P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{ Labelled { name, parser } }
@@ -0,0 +1,40 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Grammar,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult,
+    Parser,
+};
+
+pub struct Lookahead<P> (P);
+
+impl<'a, O, E, M, I, P> Parser<'a, O, E, M, I> for Lookahead<P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
+        let cursor: usize = input.store_cursor();
+        let result: ModeResult<O, E, M, _Mode> = _Mode::apply_nested(&self.0, input);
+        input.move_cursor(cursor);
+        result
+    }
+
+    implement_modes!('a, O, E, M, I);
+
+    fn describe(&self) -> Grammar { Grammar::Lookahead (Box::new(self.0.describe())) }
+
+}
+
+/// Applies a parser without consuming any input, restoring the cursor whether it succeeds or fails
+pub const fn lookahead<'a, O, E, M, I, P>(
+    parser: P,
+) -> impl Parser<'a, O, E, M, I>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{ Lookahead (parser) }
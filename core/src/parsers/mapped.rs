@@ -1,6 +1,7 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Grammar,
     implement_modes,
     Input,
     Mode,
@@ -29,13 +30,15 @@ where
         input: &'a I
     ) -> ModeResult<OB, E, M, _Mode> {
         _Mode::map_output(
-            self.parser.apply::<_Mode>(input),
+            _Mode::apply_nested(&self.parser, input),
             &self.function
         )
     }
 
     implement_modes!('a, OB, E, M, I);
 
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
 }
 
 /// Maps a parser's output to another type using a function
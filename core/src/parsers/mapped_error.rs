@@ -1,6 +1,7 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Grammar,
     implement_modes,
     Input,
     Mode,
@@ -28,13 +29,15 @@ where
         input: &'a I
     ) -> ModeResult<O, EB, M, _Mode> {
         _Mode::map_error(
-            self.parser.apply::<_Mode>(input),
+            _Mode::apply_nested(&self.parser, input),
             &self.function
         )
     }
 
     implement_modes!('a, O, EB, M, I);
 
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
 }
 
 /// Maps a parser's output to another type using a function
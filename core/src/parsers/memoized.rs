@@ -0,0 +1,136 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Doc,
+    Grammar,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+    Parser,
+    Printer,
+};
+use std::cell::{
+    Cell,
+    RefCell,
+};
+
+pub struct Memoized<P> {
+    /// The key that results of the parser are remembered with
+    key: &'static str,
+    /// The parser whose results are remembered
+    parser: P,
+}
+
+/// The remembered result of a memoized parser, with its messages if the mode that produced it keeps
+/// messages
+type Remembered<O, E, M> = (Result<O, E>, Option<Vec<M>>);
+
+/// Returns `true` if a mode keeps the messages added to its message containers
+fn keeps_messages<_Mode: Mode>() -> bool {
+    let kept: Cell<bool> = Cell::new(false);
+    let mut container: _Mode::MessageContainer<()> = _Mode::new_message_container();
+    _Mode::add_message_to_container(&mut container, ());
+    let probe: ModeResult<(), (), (), _Mode> = Success (_Mode::convert_output(()), container);
+    let _ = _Mode::map_messages(probe, |()| kept.set(true));
+    kept.get()
+}
+
+impl<'a, O, E, M, I, P> Parser<'a, O, E, M, I> for Memoized<P>
+where
+    O: Clone + 'static,
+    E: Clone + 'static,
+    M: Clone + 'static,
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
+        // modes that observe nested parsers have to apply them, so they neither reuse results nor
+        // remember them
+        let Some (table) = input.memo_table().filter(|_| !_Mode::observes_nested()) else {
+            return _Mode::apply_nested(&self.parser, input)
+        };
+        let start: usize = input.store_cursor();
+        let keeps_messages: bool = keeps_messages::<_Mode>();
+        // results remembered without their messages are applied again by modes that keep them
+        if let Some ((end, (result, messages))) = table.lookup(self.key, start)
+            .filter(|(_, (_, messages)): &(usize, Remembered<O, E, M>)| {
+                messages.is_some() || !keeps_messages
+            })
+        {
+            input.move_cursor(end);
+            let mut message_container: _Mode::MessageContainer<M> = _Mode::new_message_container();
+            for message in messages.into_iter().flatten() {
+                _Mode::add_message_to_container(&mut message_container, message)
+            }
+            return match result {
+                Ok (output) => Success (_Mode::convert_output(output), message_container),
+                Err (error) => Failure (_Mode::convert_error(error), message_container),
+            }
+        }
+        table.remember(self.key, start, || {
+            let remembered: RefCell<Option<Result<O, E>>> = RefCell::new(None);
+            let messages: RefCell<Vec<M>> = RefCell::new(Vec::new());
+            let result: ModeResult<O, E, M, _Mode> = _Mode::apply_nested(&self.parser, input);
+            let result: ModeResult<O, E, M, _Mode> = _Mode::map_output(result, |output| {
+                remembered.replace(Some (Ok (output.clone())));
+                output
+            });
+            let result: ModeResult<O, E, M, _Mode> = _Mode::map_error(result, |error| {
+                remembered.replace(Some (Err (error.clone())));
+                error
+            });
+            let result: ModeResult<O, E, M, _Mode> = _Mode::map_messages(result, |message| {
+                messages.borrow_mut().push(message.clone());
+                message
+            });
+            // modes without outputs or errors leave nothing to remember
+            let remembered: Option<Remembered<O, E, M>> = remembered.into_inner()
+                .map(|remembered| (remembered, keeps_messages.then(|| messages.into_inner())));
+            (result, remembered, input.store_cursor())
+        })
+    }
+
+    implement_modes!('a, O, E, M, I);
+
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
+}
+
+impl<O, P> Printer<O> for Memoized<P>
+where
+    P: Printer<O>,
+{
+
+    fn print(&self, output: &O) -> Option<Doc> { self.parser.print(output) }
+
+    fn print_ignored(&self) -> Option<Doc> { self.parser.print_ignored() }
+
+}
+
+/// Remembers the results of a parser with a key in inputs that keep a `MemoTable`, so they are
+/// reused when a parser with the same key is applied at the same position again, even after the
+/// input is edited elsewhere
+///
+/// Keys are used instead of parser values so that grammars can be rebuilt for every parse, and must
+/// be unique among the memoized parsers applied to an input. Results are reused as they are, so
+/// outputs that contain cursor positions are not moved when an edit moves the results they belong
+/// to.
+///
+/// Remembered results outlive the parse, so outputs, errors and messages must be `Clone + 'static`,
+/// and borrowed outputs like `&str` have to be mapped to owned values before they are memoized.
+/// Modes that observe nested parsers, like `Cst` and `Highlight`, always apply the parser so they
+/// observe the parsers within it. Results are only remembered by modes that produce outputs.
+pub const fn memoized<'a, O, E, M, I, P>(
+    parser: P,
+    key: &'static str,
+) -> Memoized<P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{ Memoized { key, parser } }
@@ -0,0 +1,64 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Grammar,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+    Parser,
+    Span,
+};
+use std::marker::PhantomData;
+
+pub struct Negated<O, F, P> {
+    /// The function that creates the error from the span of input that the parser matches
+    error: F,
+    /// The parser that must not match
+    parser: P,
+    _phantom: PhantomData<O>,
+}
+
+impl<'a, O, E, M, F, I, P> Parser<'a, (), E, M, I> for Negated<O, F, P>
+where
+    F: Fn(Span) -> E,
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<(), E, M, _Mode> {
+        let cursor: usize = input.store_cursor();
+        // only whether the parser matches matters, so it is checked without building outputs
+        let matched: bool = self.parser.check(input);
+        let span: Span = Span::new(cursor, input.store_cursor());
+        input.move_cursor(cursor);
+        if matched {
+            Failure (_Mode::convert_error((self.error)(span)), _Mode::new_message_container())
+        } else {
+            Success (_Mode::convert_output(()), _Mode::new_message_container())
+        }
+    }
+
+    implement_modes!('a, (), E, M, I);
+
+    fn describe(&self) -> Grammar { Grammar::Not (Box::new(self.parser.describe())) }
+
+}
+
+/// Succeeds without consuming any input only if a parser fails at the cursor, otherwise failing
+/// with an error created from the span of input that the parser matches
+///
+/// The parser is only checked, so it produces no outputs or messages.
+pub const fn not<'a, O, E, M, F, I, P>(
+    parser: P,
+    error: F,
+) -> impl Parser<'a, (), E, M, I>
+where
+    F: Fn(Span) -> E,
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{ Negated { error, parser, _phantom: PhantomData } }
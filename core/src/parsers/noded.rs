@@ -0,0 +1,70 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Grammar,
+    implement_modes,
+    Input,
+    IntoChildren,
+    Mode,
+    ModeResult,
+    Node,
+    Parser,
+    Span,
+};
+
+pub struct Noded<O, S, P> {
+    /// Adds the nodes contained in the output of the parser to the children of its node
+    add_children: fn(O, &mut Vec<Node<S>>),
+    /// The parser whose node is built
+    parser: P,
+    /// The name of the node
+    rule: &'static str,
+}
+
+impl<'a, O, E, M, I, P> Parser<'a, Node<I::Slice>, E, M, I> for Noded<O, I::Slice, P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<Node<I::Slice>, E, M, _Mode> {
+        let start: usize = input.store_cursor();
+        let result: ModeResult<O, E, M, _Mode> = _Mode::apply_nested(&self.parser, input);
+        let end: usize = input.store_cursor();
+        // modes without outputs never call this, so they skip building the tree
+        _Mode::map_output(result, |output| {
+            let mut children: Vec<Node<I::Slice>> = Vec::new();
+            (self.add_children)(output, &mut children);
+            let span: Span = Span::new(start, end);
+            Node { children, rule: self.rule, span, text: input.slice(start, end) }
+        })
+    }
+
+    implement_modes!('a, Node<I::Slice>, E, M, I);
+
+    fn describe(&self) -> Grammar {
+        Grammar::Rule (self.rule, Box::new(self.parser.describe()))
+    }
+
+}
+
+/// Builds a `Node` without children from the input a parser matches, ignoring its output
+pub const fn leaf<'a, O, E, M, I, P>(
+    rule: &'static str,
+    parser: P,
+) -> Noded<O, I::Slice, P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{ Noded { add_children: |_, _| {}, parser, rule } }
+
+/// Builds a `Node` from the input a parser matches, with the `Node`s in its output as children
+pub const fn node<'a, O, E, M, I, P>(
+    rule: &'static str,
+    parser: P,
+) -> Noded<O, I::Slice, P>
+where
+    O: IntoChildren<I::Slice>,
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{ Noded { add_children: O::add_children, parser, rule } }
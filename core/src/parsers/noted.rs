@@ -0,0 +1,28 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Doc,
+    Printer,
+};
+
+/// A parser combinator that attaches the messages of abandoned parser applications to its error
+/// as notes created by a function
+///
+/// A `Choice` can be converted into a `Noted` combinator with `Choice::attach_notes`.
+pub struct Noted<P, F> {
+    /// The function that turns the messages of abandoned parser applications into notes
+    pub(crate) note: F,
+    /// The parser whose abandoned applications are noted
+    pub(crate) parser: P,
+}
+
+impl<O, F, P> Printer<O> for Noted<P, F>
+where
+    P: Printer<O>,
+{
+
+    fn print(&self, output: &O) -> Option<Doc> { self.parser.print(output) }
+
+    fn print_ignored(&self) -> Option<Doc> { self.parser.print_ignored() }
+
+}
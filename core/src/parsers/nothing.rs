@@ -1,6 +1,8 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Doc,
+    Grammar,
     implement_modes,
     Input,
     Mode,
@@ -9,10 +11,11 @@ use crate::{
         Success,
     },
     Parser,
+    Printer,
 };
 use std::marker::PhantomData;
 
-pub struct Nothing<E, M> (PhantomData<(E, M)>);
+pub struct Nothing<E, M> (pub(crate) PhantomData<(E, M)>);
 
 impl<'a, E, M, I> Parser<'a, (), E, M, I> for Nothing<E, M>
 where
@@ -25,10 +28,20 @@ where
 
     implement_modes!('a, (), E, M, I);
 
+    fn describe(&self) -> Grammar { Grammar::Empty }
+
+}
+
+impl<E, M> Printer<()> for Nothing<E, M> {
+
+    fn print(&self, _: &()) -> Option<Doc> { Some (Doc::Nil) }
+
+    fn print_ignored(&self) -> Option<Doc> { Some (Doc::Nil) }
+
 }
 
 /// Parses absolutely nothing
-pub const fn nothing<'a, E, M, I>() -> impl Parser<'a, (), E, M, I>
+pub const fn nothing<'a, E, M, I>() -> impl Parser<'a, (), E, M, I> + Printer<()>
 where
     I: Input<'a>,
 { Nothing (PhantomData) }
\ No newline at end of file
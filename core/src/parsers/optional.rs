@@ -1,6 +1,9 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Doc,
+    Grammar,
+    Backtracking,
     implement_modes,
     Input,
     Mode,
@@ -10,9 +13,25 @@ use crate::{
         Success,
     },
     Parser,
+    Printer,
 };
 
-pub struct Optional<P> (P);
+pub struct Optional<P> {
+    /// The policy for messages from the parser when it fails
+    backtracking: Backtracking,
+    /// The parser that is applied optionally
+    parser: P,
+}
+
+impl<P> Optional<P> {
+
+    /// Sets the policy for messages from the parser when it fails
+    pub const fn backtracking(mut self, backtracking: Backtracking) -> Self {
+        self.backtracking = backtracking;
+        self
+    }
+
+}
 
 impl<'a, O, E, M, I, P> Parser<'a, Option<O>, E, M, I> for Optional<P>
 where
@@ -21,21 +40,46 @@ where
 {
 
     fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<Option<O>, E, M, _Mode> {
-        match self.0.apply::<_Mode>(input) {
+        match _Mode::apply_nested(&self.parser, input) {
             success @ Success (..) => _Mode::map_output(success, |output| Some (output)),
-            Failure (_, messages) => Success (_Mode::convert_output(None), messages)
+            Failure (_, messages) => self.backtracking.resolve(
+                self.backtracking.abandon::<_Mode, M>(_Mode::new_message_container(), messages),
+                Success (_Mode::convert_output(None), _Mode::new_message_container())
+            ),
         }
     }
 
     implement_modes!('a, Option<O>, E, M, I);
 
+    fn describe(&self) -> Grammar { Grammar::optional(self.parser.describe()) }
+
+}
+
+impl<O, P> Printer<Option<O>> for Optional<P>
+where
+    P: Printer<O>,
+{
+
+    fn print(&self, output: &Option<O>) -> Option<Doc> {
+        match output {
+            Some (output) => self.parser.print(output),
+            None => Some (Doc::Nil),
+        }
+    }
+
+    // ignored optional input is left out
+    fn print_ignored(&self) -> Option<Doc> { Some (Doc::Nil) }
+
 }
 
 /// Optionally applies a parser, converting a failure into `Option::None`
+///
+/// Messages from the parser when it fails are kept unless another `Backtracking` policy is set.
+/// An `Optional` parser never fails, so `Backtracking::KeepOnFailure` discards them.
 pub const fn optional<'a, O, E, M, I, P>(
     parser: P,
-) -> impl Parser<'a, Option<O>, E, M, I>
+) -> Optional<P>
 where
     I: Input<'a>,
     P: Parser<'a, O, E, M, I>,
-{ Optional (parser) }
\ No newline at end of file
+{ Optional { backtracking: Backtracking::Keep, parser } }
\ No newline at end of file
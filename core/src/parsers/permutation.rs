@@ -0,0 +1,279 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Grammar,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+    Parser,
+    Reported,
+};
+use std::cell::Cell;
+
+
+/// A message produced by a `Permutation` whose members do not each appear exactly once
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PermutationMessage {
+    /// A member that was already parsed appears again
+    Duplicated {
+        /// The index of the member in the permutation
+        member: usize,
+        /// The cursor position where the member appears again
+        position: usize,
+    },
+    /// A required member never appears
+    Missing {
+        /// The index of the member in the permutation
+        member: usize,
+        /// The cursor position where the member was expected
+        position: usize,
+    },
+}
+
+impl From<PermutationMessage> for () {
+    fn from(_: PermutationMessage) -> Self {}
+}
+
+
+/// Macro to automatically implement permutation for tuples of various sizes
+macro_rules! implement_permutation {
+    ( $( $parser:ident $output:ident )+ ) => {
+        permutation_tuple!($( $parser $output ),+);
+    };
+}
+
+macro_rules! permutation_tuple {
+    ( $( $parser:ident $output:ident ),+ ) => {
+        #[allow(non_snake_case, unused_assignments)]
+        impl<$( $parser, )+> Permutation<($( $parser, )+)> {
+            /// Applies the members of this permutation in any order, reporting how they appear
+            /// with a function if one is given
+            fn permute<'a, E, M, I, _Mode, $( $output, )+>(
+                &self,
+                input: &'a I,
+                report: Option<fn(PermutationMessage) -> M>,
+            ) -> ModeResult<($( $output, )+), E, M, _Mode>
+            where
+                I: Input<'a>,
+                _Mode: Mode,
+                $( $parser: Parser<'a, $output, E, M, I>, )+
+            {
+                let start: usize = input.store_cursor();
+                let ($( $parser, )+) = &self.0;
+                $( let mut $output: Option<_Mode::OutputForm<$output>> = None; )+
+                let mut messages: _Mode::MessageContainer<M> = _Mode::new_message_container();
+                // apply members that consume input in any order until none of the rest match
+                'rounds: loop {
+                    $(
+                        if $output.is_none() {
+                            let cursor: usize = input.store_cursor();
+                            if let Success (output, member_messages)
+                                = _Mode::apply_nested($parser, input)
+                                && input.store_cursor() > cursor {
+                                messages
+                                    = _Mode::merge_message_containers(messages, member_messages);
+                                $output = Some (output);
+                                continue 'rounds;
+                            }
+                            input.move_cursor(cursor);
+                        }
+                    )+
+                    break;
+                }
+                // report members that appear again after they were parsed
+                let cursor: usize = input.store_cursor();
+                let mut member: usize = 0;
+                $(
+                    if let Some (report) = report && $output.is_some() {
+                        // the probe is abandoned like a failed choice, so its messages are dropped
+                        let probe: Probe<'_, $parser>
+                            = Probe { end: Cell::new(cursor), parser: $parser };
+                        if _Mode::apply_nested(&probe, input).is_success()
+                            && probe.end.get() > cursor {
+                            _Mode::add_message_to_container(
+                                &mut messages,
+                                report(PermutationMessage::Duplicated { member, position: cursor })
+                            );
+                        }
+                    }
+                    member += 1;
+                )+
+                // apply remaining members, which succeed only if they can match nothing
+                let mut error: Option<_Mode::ErrorForm<E>> = None;
+                let mut member: usize = 0;
+                $(
+                    if $output.is_none() {
+                        match _Mode::apply_nested($parser, input) {
+                            Success (output, member_messages) => {
+                                messages
+                                    = _Mode::merge_message_containers(messages, member_messages);
+                                $output = Some (output);
+                            }
+                            Failure (member_error, member_messages) => {
+                                messages
+                                    = _Mode::merge_message_containers(messages, member_messages);
+                                if let Some (report) = report {
+                                    _Mode::add_message_to_container(
+                                        &mut messages,
+                                        report(PermutationMessage::Missing {
+                                            member,
+                                            position: cursor,
+                                        })
+                                    );
+                                }
+                                if error.is_none() { error = Some (member_error) }
+                            }
+                        }
+                    }
+                    member += 1;
+                )+
+                if let Some (error) = error {
+                    input.move_cursor(start);
+                    return Failure (error, messages)
+                }
+                let outputs: _Mode::OutputForm<()> = _Mode::convert_output(());
+                permutation_merge!(_Mode, outputs, (); $( $output )+);
+                Success (outputs, messages)
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<'a, E, M, I, $( $parser, $output, )+> Parser<'a, ($( $output, )+), E, M, I>
+        for Permutation<($( $parser, )+)>
+        where
+            I: Input<'a>,
+            $( $parser: Parser<'a, $output, E, M, I>, )+
+        {
+            fn apply<_Mode: Mode>(
+                &self,
+                input: &'a I
+            ) -> ModeResult<($( $output, )+), E, M, _Mode> { self.permute(input, None) }
+
+            implement_modes!('a, ($( $output, )+), E, M, I);
+
+            fn describe(&self) -> Grammar {
+                let ($( $parser, )+) = &self.0;
+                Grammar::Permutation (vec![$( $parser.describe(), )+])
+            }
+        }
+
+        impl<'a, E, M, I, $( $parser, $output, )+> Parser<'a, ($( $output, )+), E, M, I>
+        for Reported<Permutation<($( $parser, )+)>>
+        where
+            I: Input<'a>,
+            M: From<PermutationMessage>,
+            $( $parser: Parser<'a, $output, E, M, I>, )+
+        {
+            fn apply<_Mode: Mode>(
+                &self,
+                input: &'a I
+            ) -> ModeResult<($( $output, )+), E, M, _Mode> { self.0.permute(input, Some (M::from)) }
+
+            implement_modes!('a, ($( $output, )+), E, M, I);
+
+            fn describe(&self) -> Grammar { self.0.describe() }
+        }
+    }
+}
+
+/// Merges the stored output of the next member into the flat tuple of outputs
+macro_rules! permutation_merge {
+    ( $mode:ident, $outputs:ident, ( $( $done:ident )* ); ) => { };
+    ( $mode:ident, $outputs:ident, ( $( $done:ident )* ); $output:ident $( $rest:ident )* ) => {
+        let $outputs: $mode::OutputForm<($( $done, )* $output,)>
+            = $mode::merge_outputs::<($( $done, )*), $output, ($( $done, )* $output,)>(
+                $outputs,
+                $output.expect("every member of a successful permutation has an output"),
+                |($( $done, )*), $output| ($( $done, )* $output,),
+            );
+        permutation_merge!($mode, $outputs, ( $( $done )* $output ); $( $rest )*);
+    };
+}
+
+/// Applies a member of a permutation without consuming any input, storing where it ends
+struct Probe<'p, P> {
+    /// The cursor position after the member was applied
+    end: Cell<usize>,
+    /// The member being probed
+    parser: &'p P,
+}
+
+impl<'a, O, E, M, I, P> Parser<'a, O, E, M, I> for Probe<'_, P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
+        let cursor: usize = input.store_cursor();
+        let result: ModeResult<O, E, M, _Mode> = _Mode::apply_nested(self.parser, input);
+        self.end.set(input.store_cursor());
+        input.move_cursor(cursor);
+        result
+    }
+
+    implement_modes!('a, O, E, M, I);
+
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
+}
+
+pub struct Permutation<PL> (PL);
+
+impl<PL> Permutation<PL> {
+
+    /// Reports members of this permutation that are missing or appear more than once as
+    /// `PermutationMessage`s
+    pub const fn reported(self) -> Reported<Self> { Reported (self) }
+
+}
+
+implement_permutation!(P1 O1);
+implement_permutation!(P1 O1 P2 O2);
+implement_permutation!(P1 O1 P2 O2 P3 O3);
+implement_permutation!(P1 O1 P2 O2 P3 O3 P4 O4);
+implement_permutation!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5);
+implement_permutation!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6);
+implement_permutation!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7);
+implement_permutation!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8);
+
+implement_permutation!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9);
+implement_permutation!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10);
+implement_permutation!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11);
+implement_permutation!(
+    P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11 P12 O12
+);
+implement_permutation!(
+    P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11 P12 O12 P13 O13
+);
+implement_permutation!(
+    P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11 P12 O12 P13 O13
+    P14 O14
+);
+implement_permutation!(
+    P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11 P12 O12 P13 O13
+    P14 O14 P15 O15
+);
+implement_permutation!(
+    P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11 P12 O12 P13 O13
+    P14 O14 P15 O15 P16 O16
+);
+
+/// Applies each parser in a tuple of up to 16 parsers exactly once in any order, returning their
+/// outputs in declaration order
+///
+/// Members that can succeed without consuming input, such as `optional` parsers, are only applied
+/// in that way once no other member matches. Members that are missing or appear more than once are
+/// reported as `PermutationMessage`s if the permutation is `reported`.
+pub const fn permutation<'a, O, E, M, I, PL>(
+    parser_list: PL,
+) -> Permutation<PL>
+where
+    I: Input<'a>,
+    Permutation<PL>: Parser<'a, O, E, M, I>,
+{ Permutation (parser_list) }
@@ -1,12 +1,14 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Grammar,
     implement_modes,
     Input,
     Mode,
     ModeResult::{
         self,
         Failure,
+        Success,
     },
     Parser,
 };
@@ -26,20 +28,26 @@ where
 {
 
     fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
-        match self.parser.apply::<_Mode>(input) {
-            Failure (_, primary_messages) => match self.fallback.apply::<_Mode>(input) {
-                    Failure (alternate_error, alternate_messages) => Failure (
-                        alternate_error,
-                        _Mode::merge_message_containers(primary_messages, alternate_messages),
-                    ),
-                    success => success
-                }
+        match _Mode::apply_nested(&self.parser, input) {
+            Failure (_, primary_messages) => match _Mode::apply_nested(&self.fallback, input) {
+                Success (output, alternate_messages) => Success (
+                    output,
+                    _Mode::merge_message_containers(primary_messages, alternate_messages),
+                ),
+                Failure (alternate_error, alternate_messages) => Failure (
+                    alternate_error,
+                    _Mode::merge_message_containers(primary_messages, alternate_messages),
+                ),
+            }
             success => success
         }
     }
 
     implement_modes!('a, O, E, M, I);
 
+    // the fallback only recovers from invalid input, so it is not part of the grammar
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
 }
 
 /// Applies a parser, but uses another one to recover if the first fails, keeping messages from both
@@ -0,0 +1,25 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Doc,
+    Printer,
+};
+
+/// A parser combinator that reports the messages describing how its input was matched, which it
+/// does not produce otherwise
+///
+/// Combinators like `Iterated` and `Permutation` can be converted into a `Reported` combinator to
+/// produce their own message types, as long as the message type of the parser can be created from
+/// them.
+pub struct Reported<P> (pub(crate) P);
+
+impl<O, P> Printer<O> for Reported<P>
+where
+    P: Printer<O>,
+{
+
+    fn print(&self, output: &O) -> Option<Doc> { self.0.print(output) }
+
+    fn print_ignored(&self) -> Option<Doc> { self.0.print_ignored() }
+
+}
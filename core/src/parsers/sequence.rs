@@ -0,0 +1,146 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Doc,
+    Grammar,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+    Parser,
+    Printer,
+};
+
+
+/// Macro to automatically implement sequence for tuples of various sizes
+macro_rules! implement_sequence {
+    ( $( $parser:ident $output:ident )+ ) => {
+        sequence_tuple!($( $parser $output ),+);
+    };
+}
+
+macro_rules! sequence_tuple {
+    ( $( $parser:ident $output:ident ),+ ) => {
+        #[allow(non_snake_case)]
+        impl<'a, E, M, I, $( $parser, $output, )+> Parser<'a, ($( $output, )+), E, M, I>
+        for Sequence<($( $parser, )+)>
+        where
+            I: Input<'a>,
+            $( $parser: Parser<'a, $output, E, M, I>, )+
+        {
+            fn apply<_Mode: Mode>(
+                &self,
+                input: &'a I
+            ) -> ModeResult<($( $output, )+), E, M, _Mode> {
+                let cursor: usize = input.store_cursor();
+                let ($( $parser, )+) = &self.0;
+                let outputs: _Mode::OutputForm<()> = _Mode::convert_output(());
+                let mut messages: _Mode::MessageContainer<M> = _Mode::new_message_container();
+                sequence_step!(_Mode, input, cursor, outputs, messages, (); $( $parser $output )+);
+                Success (outputs, messages)
+            }
+
+            implement_modes!('a, ($( $output, )+), E, M, I);
+
+            fn describe(&self) -> Grammar {
+                let ($( $parser, )+) = &self.0;
+                Grammar::sequence([$( $parser.describe(), )+])
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<$( $parser, $output, )+> Printer<($( $output, )+)> for Sequence<($( $parser, )+)>
+        where
+            $( $parser: Printer<$output>, )+
+        {
+            fn print(&self, ($( $output, )+): &($( $output, )+)) -> Option<Doc> {
+                let ($( $parser, )+) = &self.0;
+                Some (Doc::concat([$( $parser.print($output)?, )+]))
+            }
+
+            fn print_ignored(&self) -> Option<Doc> {
+                let ($( $parser, )+) = &self.0;
+                Some (Doc::concat([$( $parser.print_ignored()?, )+]))
+            }
+        }
+    }
+}
+
+/// Applies the next parser in a sequence, appending its output to the flat tuple of outputs
+macro_rules! sequence_step {
+    (
+        $mode:ident, $input:ident, $cursor:ident, $outputs:ident, $messages:ident,
+        ( $( $done:ident )* );
+    ) => { };
+    (
+        $mode:ident, $input:ident, $cursor:ident, $outputs:ident, $messages:ident,
+        ( $( $done:ident )* ); $parser:ident $output:ident $( $rest:ident )*
+    ) => {
+        let $outputs: $mode::OutputForm<($( $done, )* $output,)>
+            = match $mode::apply_nested($parser, $input) {
+            Success (output, parser_messages) => {
+                $messages = $mode::merge_message_containers($messages, parser_messages);
+                $mode::merge_outputs::<($( $done, )*), $output, ($( $done, )* $output,)>(
+                    $outputs,
+                    output,
+                    |($( $done, )*), $output| ($( $done, )* $output,),
+                )
+            }
+            Failure (error, parser_messages) => {
+                $input.move_cursor($cursor);
+                return Failure (error, $mode::merge_message_containers($messages, parser_messages))
+            }
+        };
+        sequence_step!(
+            $mode, $input, $cursor, $outputs, $messages,
+            ( $( $done )* $output ); $( $rest )*
+        );
+    };
+}
+
+pub struct Sequence<PL> (PL);
+
+implement_sequence!(P1 O1);
+implement_sequence!(P1 O1 P2 O2);
+implement_sequence!(P1 O1 P2 O2 P3 O3);
+implement_sequence!(P1 O1 P2 O2 P3 O3 P4 O4);
+implement_sequence!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5);
+implement_sequence!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6);
+implement_sequence!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7);
+implement_sequence!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8);
+
+implement_sequence!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9);
+implement_sequence!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10);
+implement_sequence!(P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11);
+implement_sequence!(
+    P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11 P12 O12
+);
+implement_sequence!(
+    P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11 P12 O12 P13 O13
+);
+implement_sequence!(
+    P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11 P12 O12 P13 O13
+    P14 O14
+);
+implement_sequence!(
+    P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11 P12 O12 P13 O13
+    P14 O14 P15 O15
+);
+implement_sequence!(
+    P1 O1 P2 O2 P3 O3 P4 O4 P5 O5 P6 O6 P7 O7 P8 O8 P9 O9 P10 O10 P11 O11 P12 O12 P13 O13
+    P14 O14 P15 O15 P16 O16
+);
+
+/// Applies a tuple of up to 16 parsers in sequence, returning their outputs as a flat tuple and
+/// restoring the cursor if any of them fails
+pub const fn sequence<'a, O, E, M, I, PL>(
+    parser_list: PL,
+) -> Sequence<PL>
+where
+    I: Input<'a>,
+    Sequence<PL>: Parser<'a, O, E, M, I>,
+{ Sequence (parser_list) }
@@ -1,17 +1,20 @@
 // Copyright Rob Gage 2025
 
 use crate::{
+    Doc,
+    Grammar,
     implement_modes,
     Input,
     Mode,
-    mapped,
     ModeResult::{
         self,
         Success,
         Failure,
     },
     Parser,
+    Printer,
 };
+use std::marker::PhantomData;
 
 pub struct Sequenced<P1, P2> {
     /// The head of the `Sequence`
@@ -32,8 +35,8 @@ where
         input: &'a I
     ) -> ModeResult<(O1, O2), E, M, _Mode> {
         let cursor = input.store_cursor();
-        match self.head.apply::<_Mode>(input) {
-            Success (head_output, head_messages) => match self.tail.apply::<_Mode>(input) {
+        match _Mode::apply_nested(&self.head, input) {
+            Success (head_output, head_messages) => match _Mode::apply_nested(&self.tail, input) {
                 Success (tail_output, tail_messages) => {
                     Success (
                         _Mode::merge_outputs::<O1, O2, (O1, O2)>(
@@ -57,15 +60,111 @@ where
 
     implement_modes!('a, (O1, O2), E, M, I);
 
+    fn describe(&self) -> Grammar {
+        Grammar::sequence([self.head.describe(), self.tail.describe()])
+    }
+
+}
+
+impl<O1, O2, P1, P2> Printer<(O1, O2)> for Sequenced<P1, P2>
+where
+    P1: Printer<O1>,
+    P2: Printer<O2>,
+{
+
+    fn print(&self, (head, tail): &(O1, O2)) -> Option<Doc> {
+        Some (Doc::concat([self.head.print(head)?, self.tail.print(tail)?]))
+    }
+
+    fn print_ignored(&self) -> Option<Doc> {
+        Some (Doc::concat([self.head.print_ignored()?, self.tail.print_ignored()?]))
+    }
+
+}
+
+pub struct Preceded<O1, P1, P2> {
+    /// The prefix and the parser that are applied in sequence
+    sequenced: Sequenced<P1, P2>,
+    _phantom: PhantomData<O1>,
+}
+
+impl<'a, O1, O2, E, M, I, P1, P2> Parser<'a, O2, E, M, I> for Preceded<O1, P1, P2>
+where
+    I: Input<'a>,
+    P1: Parser<'a, O1, E, M, I>,
+    P2: Parser<'a, O2, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O2, E, M, _Mode> {
+        _Mode::map_output(_Mode::apply_nested(&self.sequenced, input), |(_, output)| output)
+    }
+
+    implement_modes!('a, O2, E, M, I);
+
+    fn describe(&self) -> Grammar { self.sequenced.describe() }
+
+}
+
+impl<O1, O2, P1, P2> Printer<O2> for Preceded<O1, P1, P2>
+where
+    P1: Printer<O1>,
+    P2: Printer<O2>,
+{
+
+    fn print(&self, output: &O2) -> Option<Doc> {
+        let prefix: Doc = self.sequenced.head.print_ignored()?;
+        Some (Doc::concat([prefix, self.sequenced.tail.print(output)?]))
+    }
+
+    fn print_ignored(&self) -> Option<Doc> { self.sequenced.print_ignored() }
+
+}
+
+pub struct Terminated<O2, P1, P2> {
+    /// The parser and the terminator that are applied in sequence
+    sequenced: Sequenced<P1, P2>,
+    _phantom: PhantomData<O2>,
+}
+
+impl<'a, O1, O2, E, M, I, P1, P2> Parser<'a, O1, E, M, I> for Terminated<O2, P1, P2>
+where
+    I: Input<'a>,
+    P1: Parser<'a, O1, E, M, I>,
+    P2: Parser<'a, O2, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O1, E, M, _Mode> {
+        _Mode::map_output(_Mode::apply_nested(&self.sequenced, input), |(output, _)| output)
+    }
+
+    implement_modes!('a, O1, E, M, I);
+
+    fn describe(&self) -> Grammar { self.sequenced.describe() }
+
+}
+
+impl<O1, O2, P1, P2> Printer<O1> for Terminated<O2, P1, P2>
+where
+    P1: Printer<O1>,
+    P2: Printer<O2>,
+{
+
+    fn print(&self, output: &O1) -> Option<Doc> {
+        let terminator: Doc = self.sequenced.tail.print_ignored()?;
+        Some (Doc::concat([self.sequenced.head.print(output)?, terminator]))
+    }
+
+    fn print_ignored(&self) -> Option<Doc> { self.sequenced.print_ignored() }
+
 }
 
 /// Applies a parser preceded by an ignored prefix parser, and followed by an ignored terminator
 /// parser
-pub fn delimited<'a, O1, O2, O3, E, M, I, P1, P2, P3>(
+pub const fn delimited<'a, O1, O2, O3, E, M, I, P1, P2, P3>(
     prefix: P1,
     parser: P2,
     terminator: P3,
-) -> impl Parser<'a, O2, E, M, I>
+) -> Preceded<O1, P1, Terminated<O3, P2, P3>>
 where
     I: Input<'a>,
     P1: Parser<'a, O1, E, M, I>,
@@ -77,18 +176,18 @@ where
 pub const fn preceded<'a, O1, O2, E,  M, I, P1, P2>(
     prefix: P1,
     parser: P2
-) -> impl Parser<'a, O2, E, M, I>
+) -> Preceded<O1, P1, P2>
 where
     I: Input<'a>,
     P1: Parser<'a, O1, E, M, I>,
     P2: Parser<'a, O2, E, M, I>,
-{ mapped(sequenced(prefix, parser), |(_, output)| output) }
+{ Preceded { sequenced: Sequenced { head: prefix, tail: parser }, _phantom: PhantomData } }
 
 /// Applies a parser followed by another parser, and returns the outputs as a tuple
 pub const fn sequenced<'a, O1, O2, E, M, I, P1, P2>(
     first: P1,
     second: P2
-) -> impl Parser<'a, (O1, O2), E, M, I>
+) -> Sequenced<P1, P2>
 where
     I: Input<'a>,
     P1: Parser<'a, O1, E, M, I>,
@@ -99,9 +198,9 @@ where
 pub const fn terminated<'a, O1, O2, E, M, I, P1, P2>(
     parser: P1,
     terminator: P2
-) -> impl Parser<'a, O1, E, M, I>
+) -> Terminated<O2, P1, P2>
 where
     I: Input<'a>,
     P1: Parser<'a, O1, E, M, I>,
     P2: Parser<'a, O2, E, M, I>,
-{ mapped(sequenced(parser, terminator), |(output, _)| output) }
\ No newline at end of file
+{ Terminated { sequenced: Sequenced { head: parser, tail: terminator }, _phantom: PhantomData } }
\ No newline at end of file
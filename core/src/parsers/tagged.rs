@@ -0,0 +1,81 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Annotation,
+    Doc,
+    Grammar,
+    HighlightClass,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult,
+    Parser,
+    Printer,
+    SyntaxTag,
+};
+
+pub struct Tagged<P> {
+    /// The parser that is tagged
+    parser: P,
+    /// The role of the parser in a concrete syntax tree
+    tag: SyntaxTag,
+}
+
+impl<'a, O, E, M, I, P> Parser<'a, O, E, M, I> for Tagged<P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
+        _Mode::apply_annotated(Annotation::Syntax (self.tag), &self.parser, input)
+    }
+
+    implement_modes!('a, O, E, M, I);
+
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
+}
+
+impl<O, P> Printer<O> for Tagged<P>
+where
+    P: Printer<O>,
+{
+
+    fn print(&self, output: &O) -> Option<Doc> { self.parser.print(output) }
+
+    fn print_ignored(&self) -> Option<Doc> { self.parser.print_ignored() }
+
+}
+
+/// Tags a parser so the input it consumes is highlighted with a class, becoming a single token of
+/// the class's kind in concrete syntax trees
+pub const fn highlighted<'a, O, E, M, I, P>(
+    parser: P,
+    class: HighlightClass,
+) -> Tagged<P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{ Tagged { parser, tag: class.tag() } }
+
+/// Tags a parser so the input it consumes becomes a single token of a kind in concrete syntax trees
+pub const fn syntax_token<'a, O, E, M, I, P>(
+    parser: P,
+    kind: &'static str,
+) -> Tagged<P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{ Tagged { parser, tag: SyntaxTag::Token (kind) } }
+
+/// Tags a parser so the input it consumes becomes a single trivia token of a kind, like a comment,
+/// in concrete syntax trees
+pub const fn trivia<'a, O, E, M, I, P>(
+    parser: P,
+    kind: &'static str,
+) -> Tagged<P>
+where
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{ Tagged { parser, tag: SyntaxTag::Trivia (kind) } }
@@ -1,20 +1,47 @@
 // Copyright Rob Gage 2025
 
-// Copyright Rob Gage 2025
-
 use crate::{
+    Annotation,
+    Grammar,
     implement_modes,
     Input,
     Mode,
-    ModeResult::{
-        self,
-        Failure,
-        Success,
-    },
-    Verbose,
+    ModeResult,
     Parser,
 };
-use std::fmt::Debug;
+
+/// Applies a named parser within a `tracing` span recording where it starts and ends and whether it
+/// matched, if the `tracing` feature is enabled
+pub(crate) fn report_traced<'a, O, E, M, I, _Mode>(
+    name: &'static str,
+    input: &'a I,
+    apply: impl FnOnce() -> ModeResult<O, E, M, _Mode>,
+) -> ModeResult<O, E, M, _Mode>
+where
+    I: Input<'a>,
+    _Mode: Mode,
+{
+    #[cfg(feature = "tracing")]
+    {
+        let span: tracing::Span = tracing::debug_span!(
+            "parser",
+            name,
+            start = input.store_cursor(),
+            end = tracing::field::Empty,
+            outcome = tracing::field::Empty,
+        );
+        let _entered: tracing::span::Entered = span.enter();
+        let result: ModeResult<O, E, M, _Mode> = apply();
+        span.record("end", input.store_cursor());
+        span.record("outcome", if result.is_success() { "matched" } else { "failed" });
+        result
+    }
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = (name, input);
+        apply()
+    }
+}
 
 pub struct Traced<P> {
     parser: P,
@@ -23,55 +50,29 @@ pub struct Traced<P> {
 
 impl<'a, O, E, M, I, P> Parser<'a, O, E, M, I> for Traced<P>
 where
-    O: Debug,
-    E: Debug,
     I: Input<'a>,
     P: Parser<'a, O, E, M, I>,
 {
 
     fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
-        _Mode::apply_parser(self, input)
+        report_traced(self.name, input, || {
+            _Mode::apply_annotated(Annotation::Traced (self.name), &self.parser, input)
+        })
     }
 
-    fn check(&self, input: &'a I) -> bool { self.parse(input).is_ok() }
+    implement_modes!('a, O, E, M, I);
 
-    fn parse(&self, input: &'a I) -> Result<O, E> {
-        match self.parser.parse(input) {
-            Ok (output) => {
-                println!("{} successfully parsed output: {:?}", self.name, output);
-                Ok (output)
-            }
-            Err (error) => {
-                eprintln!("{} encountered error: {:?}", self.name, error);
-                Err (error)
-            }
-        }
-    }
-
-    fn verbose(&self, input: &'a I) -> (Result<O, E>, Vec<M>) {
-        let (result, messages) = self.parser.verbose(&input);
-        match result {
-            Ok (output) => {
-                println!("{} successfully parsed output: {:?}", self.name, output);
-                (Ok (output), messages)
-            }
-            Err (error) => {
-                eprintln!("{} encountered error: {:?}", self.name, error);
-                (Err (error), messages)
-            }
-        }
-    }
+    fn describe(&self) -> Grammar { self.parser.describe() }
 
 }
 
-/// Applies a parser and returns its error or output as a status message with a name
+/// Applies a parser with a name that identifies it in `Trace` mode, and in `tracing` spans if the
+/// `tracing` feature is enabled
 pub const fn traced<'a, O, E, M, I, P>(
     parser: P,
     name: &'static str,
 ) -> impl Parser<'a, O, E, M, I>
 where
-    O: Debug,
-    E: Debug,
     I: Input<'a>,
     P: Parser<'a, O, E, M, I>,
-{ Traced { parser, name } }
\ No newline at end of file
+{ Traced { parser, name } }
@@ -0,0 +1,79 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Grammar,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult,
+    Parser,
+    Span,
+};
+use std::marker::PhantomData;
+
+/// The error produced when a filtered parser's output is rejected
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rejected {
+    /// The label describing what the filter expected
+    pub label: &'static str,
+    /// The span of the rejected output
+    pub span: Span,
+}
+
+impl From<Rejected> for () {
+    fn from(_: Rejected) -> Self {}
+}
+
+pub struct TryMapped<OA, F, P> {
+    /// The parser whose output is mapped
+    parser: P,
+    /// The fallible function used to map the output of the parser
+    function: F,
+    _phantom: PhantomData<OA>,
+}
+
+impl<'a, OA, OB, E, M, F, I, P> Parser<'a, OB, E, M, I> for TryMapped<OA, F, P>
+where
+    F: Fn(OA, Span) -> Result<OB, E>,
+    I: Input<'a>,
+    P: Parser<'a, OA, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<OB, E, M, _Mode> {
+        let start: usize = input.store_cursor();
+        _Mode::apply_validated(&self.parser, input, |output, _| {
+            (self.function)(output, Span::new(start, input.store_cursor()))
+        })
+    }
+
+    implement_modes!('a, OB, E, M, I);
+
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
+}
+
+/// Fails if a parser's output does not satisfy a predicate, with an error labelled with what was
+/// expected
+pub const fn filter<'a, O, E, M, I>(
+    parser: impl Parser<'a, O, E, M, I>,
+    label: &'static str,
+    predicate: impl Fn(&O) -> bool,
+) -> impl Parser<'a, O, E, M, I>
+where
+    E: From<Rejected>,
+    I: Input<'a>,
+{
+    try_map(parser, move |output, span| if predicate(&output) { Ok (output) } else {
+        Err (Rejected { label, span }.into())
+    })
+}
+
+/// Maps a parser's output to another type using a function that can fail, restoring the cursor if
+/// it does
+pub const fn try_map<'a, OA, OB, E, M, I>(
+    parser: impl Parser<'a, OA, E, M, I>,
+    function: impl Fn(OA, Span) -> Result<OB, E>,
+) -> impl Parser<'a, OB, E, M, I>
+where
+    I: Input<'a>,
+{ TryMapped { parser, function, _phantom: PhantomData } }
@@ -0,0 +1,68 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Grammar,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult,
+    Parser,
+    Span,
+};
+use std::marker::PhantomData;
+
+/// Collects messages emitted while validating a parser's output
+pub struct Emitter<'m, M> (Option<&'m mut Vec<M>>);
+
+impl<'m, M> Emitter<'m, M> {
+
+    /// Creates an `Emitter` that collects messages into a `Vec`
+    pub const fn new(messages: &'m mut Vec<M>) -> Self { Self (Some (messages)) }
+
+    /// Creates an `Emitter` that discards messages, for modes that do not produce them
+    pub const fn discarding() -> Self { Self (None) }
+
+    /// Emits a message alongside the validated output
+    pub fn emit(&mut self, message: M) {
+        if let Some (messages) = &mut self.0 { messages.push(message) }
+    }
+
+}
+
+pub struct Validated<OA, F, P> {
+    /// The parser whose output is validated
+    parser: P,
+    /// The function used to validate the output of the parser
+    function: F,
+    _phantom: PhantomData<OA>,
+}
+
+impl<'a, OA, OB, E, M, F, I, P> Parser<'a, OB, E, M, I> for Validated<OA, F, P>
+where
+    F: Fn(OA, Span, &mut Emitter<M>) -> OB,
+    I: Input<'a>,
+    P: Parser<'a, OA, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<OB, E, M, _Mode> {
+        let start: usize = input.store_cursor();
+        _Mode::apply_validated(&self.parser, input, |output, emitter| {
+            Ok ((self.function)(output, Span::new(start, input.store_cursor()), emitter))
+        })
+    }
+
+    implement_modes!('a, OB, E, M, I);
+
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
+}
+
+/// Validates a parser's output using a function that maps it and can emit messages, without ever
+/// failing
+pub const fn validate<'a, OA, OB, E, M, I>(
+    parser: impl Parser<'a, OA, E, M, I>,
+    function: impl Fn(OA, Span, &mut Emitter<M>) -> OB,
+) -> impl Parser<'a, OB, E, M, I>
+where
+    I: Input<'a>,
+{ Validated { parser, function, _phantom: PhantomData } }
@@ -0,0 +1,79 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Grammar,
+    implement_modes,
+    Input,
+    Mode,
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+    nothing,
+    Parser,
+};
+
+pub struct Warned<F1, F2, P> {
+    /// The function that creates a message from the output of the parser
+    message: F2,
+    /// The parser whose output may cause a message
+    parser: P,
+    /// The predicate that decides whether the output of the parser causes a message
+    predicate: F1,
+}
+
+impl<'a, O, E, M, F1, F2, I, P> Parser<'a, O, E, M, I> for Warned<F1, F2, P>
+where
+    F1: Fn(&O) -> bool,
+    F2: Fn(&O) -> M,
+    I: Input<'a>,
+    P: Parser<'a, O, E, M, I>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a I) -> ModeResult<O, E, M, _Mode> {
+        match _Mode::apply_nested(&self.parser, input) {
+            Success (output, mut messages) => {
+                _Mode::add_message_from_output(&output, &mut messages, |output| {
+                    if (self.predicate)(output) { Some ((self.message)(output)) } else { None }
+                });
+                Success (output, messages)
+            }
+            Failure (error, messages) => Failure (error, messages),
+        }
+    }
+
+    implement_modes!('a, O, E, M, I);
+
+    fn describe(&self) -> Grammar { self.parser.describe() }
+
+}
+
+/// Emits a message whenever a parser succeeds
+pub fn deprecated<'a, O, E, M, I>(
+    parser: impl Parser<'a, O, E, M, I>,
+    message: M,
+) -> impl Parser<'a, O, E, M, I>
+where
+    I: Input<'a>,
+    M: Clone,
+{ warn_if(parser, |_| true, move |_| message.clone()) }
+
+/// Emits a message without consuming any input
+pub fn emit_message<'a, E, M, I>(
+    message: M,
+) -> impl Parser<'a, (), E, M, I>
+where
+    I: Input<'a>,
+    M: Clone,
+{ deprecated(nothing(), message) }
+
+/// Emits a message created from a parser's output if the output satisfies a predicate
+pub const fn warn_if<'a, O, E, M, I>(
+    parser: impl Parser<'a, O, E, M, I>,
+    predicate: impl Fn(&O) -> bool,
+    message: impl Fn(&O) -> M,
+) -> impl Parser<'a, O, E, M, I>
+where
+    I: Input<'a>,
+{ Warned { message, parser, predicate } }
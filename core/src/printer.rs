@@ -0,0 +1,29 @@
+// Copyright Rob Gage 2025
+
+mod doc;
+
+pub use doc::Doc;
+
+
+/// Implementors can print the outputs of a parser back into the input that it would parse them
+/// from, as a `Doc` that is laid out as text
+///
+/// Parsers that ignore the outputs of other parsers, like `preceded` and `separated`, print those
+/// parsers with `Printer::print_ignored`, so only parsers with a canonical form, like tokens, can
+/// be ignored by printable parsers.
+pub trait Printer<O> {
+
+    /// Prints an output, returning `None` if this parser could not have parsed it
+    fn print(&self, output: &O) -> Option<Doc>;
+
+    /// Prints the canonical form of the input this parser matches when its output is ignored,
+    /// returning `None` if there is not one
+    fn print_ignored(&self) -> Option<Doc> { None }
+
+    /// Prints an output and lays it out as text that is no wider than a number of columns where
+    /// possible
+    fn pretty(&self, output: &O, width: usize) -> Option<String> {
+        self.print(output).map(|doc| doc.render(width))
+    }
+
+}
@@ -0,0 +1,132 @@
+// Copyright Rob Gage 2025
+
+
+/// A document that is laid out as text, breaking lines only where a group does not fit in the
+/// width it is rendered at
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Doc {
+    /// Documents that follow each other
+    Concat (Vec<Doc>),
+    /// A document that is laid out on one line if it fits, with all of its line breaks taken
+    /// otherwise
+    Group (Box<Doc>),
+    /// A line break that is always taken
+    HardLine,
+    /// A line break, or some text if the group it is part of is laid out on one line
+    Line (&'static str),
+    /// A document with its line breaks indented by a number of columns
+    Nest (usize, Box<Doc>),
+    /// Nothing
+    Nil,
+    /// Text without line breaks
+    Text (String),
+}
+
+impl Doc {
+
+    /// Creates a `Doc::Concat`, merging documents that are concatenations themselves and leaving
+    /// out empty documents
+    pub fn concat(docs: impl IntoIterator<Item = Doc>) -> Self {
+        let mut merged: Vec<Doc> = Vec::new();
+        for doc in docs {
+            match doc {
+                Doc::Concat (nested) => merged.extend(nested),
+                Doc::Nil => {}
+                doc => merged.push(doc),
+            }
+        }
+        match merged.len() {
+            0 => Doc::Nil,
+            1 => merged.remove(0),
+            _ => Doc::Concat (merged),
+        }
+    }
+
+    /// Creates a `Doc::Group`
+    pub fn group(doc: Doc) -> Self { Doc::Group (Box::new(doc)) }
+
+    /// Creates a `Doc::HardLine`
+    pub const fn hard_line() -> Self { Doc::HardLine }
+
+    /// Creates a `Doc::Line` that is a space when its group is laid out on one line
+    pub const fn line() -> Self { Doc::Line (" ") }
+
+    /// Creates a `Doc::Nest`
+    pub fn nest(indent: usize, doc: Doc) -> Self { Doc::Nest (indent, Box::new(doc)) }
+
+    /// Creates a `Doc::Nil`
+    pub const fn nil() -> Self { Doc::Nil }
+
+    /// Creates a `Doc::Line` that is nothing when its group is laid out on one line
+    pub const fn soft_line() -> Self { Doc::Line ("") }
+
+    /// Creates a `Doc::Text`, with the line breaks in the text becoming `Doc::HardLine`s
+    pub fn text(text: impl Into<String>) -> Self {
+        let text: String = text.into();
+        if !text.contains('\n') { return Doc::Text (text) }
+        let lines = text.split('\n').map(|line| Doc::Text (line.to_string()));
+        Doc::concat(lines.enumerate().flat_map(|(index, line)| {
+            [if index == 0 { Doc::Nil } else { Doc::HardLine }, line]
+        }))
+    }
+
+    /// Returns this document followed by another
+    pub fn append(self, other: Doc) -> Self { Doc::concat([self, other]) }
+
+    /// Lays this document out as text that is no wider than a number of columns where possible
+    pub fn render(&self, width: usize) -> String {
+        let mut text: String = String::new();
+        let mut column: usize = 0;
+        // the documents left to lay out, with their indentation and whether they are on one line
+        let mut stack: Vec<(usize, bool, &Doc)> = vec![(0, false, self)];
+        while let Some ((indent, flat, doc)) = stack.pop() {
+            match doc {
+                Doc::Concat (docs)
+                    => stack.extend(docs.iter().rev().map(|doc| (indent, flat, doc))),
+                Doc::Group (doc) => {
+                    let flat: bool = flat || fits(width as isize - column as isize, doc, &stack);
+                    stack.push((indent, flat, doc));
+                }
+                Doc::Line (alternative) if flat => {
+                    text.push_str(alternative);
+                    column += alternative.chars().count();
+                }
+                Doc::HardLine | Doc::Line (_) => {
+                    text.truncate(text.trim_end_matches(' ').len());
+                    text.push('\n');
+                    text.extend((0..indent).map(|_| ' '));
+                    column = indent;
+                }
+                Doc::Nest (nested, doc) => stack.push((indent + nested, flat, doc)),
+                Doc::Nil => {}
+                Doc::Text (content) => {
+                    text.push_str(content);
+                    column += content.chars().count();
+                }
+            }
+        }
+        text
+    }
+
+}
+
+/// Returns `true` if a group fits on the rest of the line when it is laid out on one line, along
+/// with the documents after it up to their next line break
+fn fits(mut remaining: isize, group: &Doc, stack: &[(usize, bool, &Doc)]) -> bool {
+    let mut rest = stack.iter().rev().map(|(_, flat, doc)| (*flat, *doc));
+    let mut pending: Vec<(bool, &Doc)> = vec![(true, group)];
+    while remaining >= 0 {
+        let Some ((flat, doc)) = pending.pop().or_else(|| rest.next()) else { return true };
+        match doc {
+            Doc::Concat (docs) => pending.extend(docs.iter().rev().map(|doc| (flat, doc))),
+            Doc::Group (doc) | Doc::Nest (_, doc) => pending.push((flat, doc)),
+            // a group with a line break that is always taken cannot be laid out on one line
+            Doc::HardLine => return !flat,
+            Doc::Line (alternative) if flat => remaining -= alternative.chars().count() as isize,
+            Doc::Line (_) => return true,
+            Doc::Nil => {}
+            Doc::Text (content) => remaining -= content.chars().count() as isize,
+        }
+    }
+    false
+}
@@ -0,0 +1,23 @@
+// Copyright Rob Gage 2025
+
+/// A range of cursor positions in an `Input`, from an inclusive start to an exclusive end
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Span {
+    /// The cursor position where the `Span` starts
+    pub start: usize,
+    /// The cursor position where the `Span` ends
+    pub end: usize,
+}
+
+impl Span {
+
+    /// Creates a new `Span` from a start cursor position to an end cursor position
+    pub const fn new(start: usize, end: usize) -> Self { Self { start, end } }
+
+    /// Returns `true` if this `Span` contains no positions
+    pub const fn is_empty(&self) -> bool { self.end <= self.start }
+
+    /// The number of positions in this `Span`
+    pub const fn length(&self) -> usize { self.end.saturating_sub(self.start) }
+
+}
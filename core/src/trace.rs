@@ -0,0 +1,318 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Annotation,
+    Emitter,
+    Input,
+    Mode,
+    modes::{
+        from_verbose,
+        validate_output,
+    },
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+    Parser,
+};
+use std::{
+    any::type_name,
+    cell::RefCell,
+    fmt::{
+        Debug,
+        Display,
+        Formatter,
+        Result as FormatResult,
+        Write,
+    },
+    mem::take,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+thread_local! {
+    /// The trace nodes of parser invocations that have started but not finished on this thread
+    static OPEN_NODES: RefCell<Vec<TraceNode>> = const { RefCell::new(Vec::new()) };
+}
+
+
+/// A record of one parser invocation in a parse trace, along with the invocations nested in it
+#[derive(Clone, Debug)]
+pub struct TraceNode {
+    /// The invocations of parsers nested within this one, in order
+    pub children: Vec<TraceNode>,
+    /// How long the invocation took, including nested invocations
+    pub duration: Duration,
+    /// The cursor position after the invocation
+    pub end: usize,
+    /// The name given to the parser with `traced`, or the name of its type
+    pub name: &'static str,
+    /// The cursor position before the invocation
+    pub start: usize,
+    /// Whether the invocation succeeded
+    pub success: bool,
+}
+
+impl TraceNode {
+
+    /// Creates a new `TraceNode` for an invocation that has not finished yet
+    const fn open(name: &'static str, start: usize) -> Self {
+        Self {
+            children: Vec::new(),
+            duration: Duration::ZERO,
+            end: start,
+            name,
+            start,
+            success: false,
+        }
+    }
+
+    /// The number of invocations recorded in this trace, including this one
+    pub fn size(&self) -> usize {
+        1 + self.children.iter().map(TraceNode::size).sum::<usize>()
+    }
+
+    /// Writes this trace as JSON, with durations in nanoseconds
+    pub fn to_json(&self) -> String {
+        let mut json: String = String::new();
+        self.write_json(&mut json).expect("writing to a `String` does not fail");
+        json
+    }
+
+    /// Writes this trace as JSON to a writer
+    fn write_json(&self, writer: &mut impl Write) -> FormatResult {
+        write!(writer, "{{\"name\":\"")?;
+        for character in self.name.chars() {
+            match character {
+                '"' => writer.write_str("\\\"")?,
+                '\\' => writer.write_str("\\\\")?,
+                character if character.is_control()
+                    => write!(writer, "\\u{:04x}", character as u32)?,
+                character => writer.write_char(character)?,
+            }
+        }
+        write!(
+            writer,
+            "\",\"start\":{},\"end\":{},\"success\":{},\"duration\":{},\"children\":[",
+            self.start,
+            self.end,
+            self.success,
+            self.duration.as_nanos(),
+        )?;
+        for (index, child) in self.children.iter().enumerate() {
+            if index > 0 { writer.write_char(',')? }
+            child.write_json(writer)?;
+        }
+        writer.write_str("]}")
+    }
+
+    /// Writes this trace as indented text to a formatter, one invocation per line
+    fn write_text(&self, formatter: &mut Formatter, depth: usize) -> FormatResult {
+        writeln!(
+            formatter,
+            "{:indent$}{} [{}..{}] {} in {:?}",
+            "",
+            self.name,
+            self.start,
+            self.end,
+            if self.success { "matched" } else { "failed" },
+            self.duration,
+            indent = depth * 2,
+        )?;
+        for child in &self.children { child.write_text(formatter, depth + 1)? }
+        Ok (())
+    }
+
+}
+
+impl Display for TraceNode {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult { self.write_text(formatter, 0) }
+}
+
+
+/// Parser mode that extracts all information from the parser's input like `Verbose`, while
+/// recording a tree of every parser invocation
+///
+/// Traces are collected with `Trace::run`. Parsers behind a `dyn Parser` are applied verbosely, so
+/// they appear in the trace without the invocations nested within them.
+pub struct Trace;
+
+impl Trace {
+
+    /// Applies a parser in this mode, returning its result and the trace of its invocation
+    pub fn run<'a, O, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+    ) -> (ModeResult<O, E, M, Self>, TraceNode)
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        // keep the nodes of an enclosing run separate so runs can be nested
+        let enclosing: Vec<TraceNode> = OPEN_NODES.with_borrow_mut(|nodes| {
+            let enclosing: Vec<TraceNode> = take(nodes);
+            nodes.push(TraceNode::open("", input.store_cursor()));
+            enclosing
+        });
+        let result: ModeResult<O, E, M, Self> = Self::apply_nested(parser, input);
+        let mut collector: TraceNode = OPEN_NODES.with_borrow_mut(|nodes| {
+            let collector: TraceNode = nodes.pop().expect("the collector node is still open");
+            *nodes = enclosing;
+            collector
+        });
+        let root: TraceNode = collector.children.pop().expect("the parser invocation was recorded");
+        (result, root)
+    }
+
+}
+
+/// Returns the name of a type without its module path or generic parameters
+fn short_type_name<T>() -> &'static str {
+    let name: &'static str = type_name::<T>();
+    let name: &'static str = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+impl Mode for Trace {
+
+    type OutputForm<O> = O;
+
+    type ErrorForm<E> = E;
+
+    type MessageContainer<M> = Vec<M>;
+
+    fn apply_parser<'a, O, E, M, I, P>(parser: &P, input: &'a I) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I> + ?Sized,
+    { from_verbose(parser.apply_trace(input)) }
+
+    fn observes_nested() -> bool { true }
+
+    fn apply_nested<'a, O, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+    ) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        let start: usize = input.store_cursor();
+        let name: &'static str = short_type_name::<P>();
+        OPEN_NODES.with_borrow_mut(|nodes| nodes.push(TraceNode::open(name, start)));
+        let started: Instant = Instant::now();
+        let result: ModeResult<O, E, M, Self> = parser.apply::<Self>(input);
+        let duration: Duration = started.elapsed();
+        OPEN_NODES.with_borrow_mut(|nodes| {
+            let Some (mut node) = nodes.pop() else { return };
+            node.duration = duration;
+            node.end = input.store_cursor();
+            node.success = result.is_success();
+            // invocations outside of `Trace::run` have no parent, so they are not recorded
+            if let Some (parent) = nodes.last_mut() { parent.children.push(node) }
+        });
+        result
+    }
+
+    fn apply_validated<'a, OA, OB, E, M, I, P>(
+        parser: &P,
+        input: &'a I,
+        function: impl FnOnce(OA, &mut Emitter<M>) -> Result<OB, E>,
+    ) -> ModeResult<OB, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, OA, E, M, I>,
+    { validate_output(parser, input, function) }
+
+    fn apply_annotated<'a, O, E, M, I, P>(
+        annotation: Annotation,
+        parser: &P,
+        input: &'a I,
+    ) -> ModeResult<O, E, M, Self>
+    where
+        I: Input<'a>,
+        P: Parser<'a, O, E, M, I>,
+    {
+        let Annotation::Traced (name) = annotation else {
+            return Self::apply_nested(parser, input)
+        };
+        // the invocation of the `Traced` parser itself is the innermost open node
+        OPEN_NODES.with_borrow_mut(|nodes| {
+            if let Some (node) = nodes.last_mut() { node.name = name }
+        });
+        parser.apply::<Self>(input)
+    }
+
+    fn convert_output<O>(output: impl Into<O>) -> O { output.into() }
+
+    fn convert_error<E>(error: impl Into<E>) -> E  { error.into() }
+
+    fn merge_outputs<OA1, OA2, OB>(
+        output_1: OA1,
+        output_2: OA2,
+        function: impl Fn(OA1, OA2) -> OB
+    ) -> OB { function(output_1, output_2) }
+
+    fn merge_errors<EA1, EA2, EB>(
+        error_1: EA1,
+        error_2: EA2,
+        function: impl Fn(EA1, EA2) -> EB
+    ) -> EB { function(error_1, error_2) }
+
+    fn merge_message_containers<M>(
+        a: Vec<M>,
+        b: impl Into<Vec<M>>
+    ) -> Vec<M> {
+        let mut a: Vec<M> = a;
+        a.extend(b.into());
+        a
+    }
+
+    fn map_output<OA, OB, E, M>(
+        result: ModeResult<OA, E, M, Self>,
+        function: impl Fn(OA) -> OB,
+    ) -> ModeResult<OB, E, M, Self> {
+        match result {
+            Success (output, messages) => Success (function(output), messages),
+            Failure (error, messages) => Failure (error, messages),
+        }
+    }
+
+    fn map_error<O, EA, EB, M>(
+        result: ModeResult<O, EA, M, Self>,
+        function: impl Fn(EA) -> EB,
+    ) -> ModeResult<O, EB, M, Self> {
+        match result {
+            Success (output, messages) => Success (output, messages),
+            Failure (error, messages) => Failure (function(error), messages),
+        }
+    }
+
+    fn map_messages<O, E, MA, MB>(
+        result: ModeResult<O, E, MA, Self>,
+        function: impl Fn(MA) -> MB,
+    ) -> ModeResult<O, E, MB, Self> {
+        match result {
+            Success (output, messages) => Success (output, messages.into_iter()
+                .map(function).collect::<Vec<MB>>()),
+            Failure (error, messages) => Failure (error, messages.into_iter()
+                .map(function).collect::<Vec<MB>>()),
+        }
+    }
+
+    fn new_message_container<M>() -> Vec<M> { Vec::new() }
+
+    fn add_message_to_container<M>(container: &mut Vec<M>, message: M) { container.push(message) }
+
+    fn add_message_from_output<O, M>(
+        output: &O,
+        container: &mut Vec<M>,
+        function: impl FnOnce(&O) -> Option<M>,
+    ) {
+        if let Some (message) = function(output) { container.push(message) }
+    }
+
+}
@@ -0,0 +1,79 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::{
+    Input,
+    prelude::*,
+};
+
+
+/// Emits a message, then parses one expected character
+fn warned<'a>(
+    message: &'static str,
+    expected: char,
+) -> impl Parser<'a, char, (), String, Characters> {
+    sequence((
+        emit_message(message.to_string()),
+        character(expected).map_messages(|()| unreachable!("characters produce no messages")),
+    )).map(|((), character)| character)
+}
+
+
+#[test]
+fn optional_parsers_keep_messages_from_failures_by_default() {
+    let input: Characters = Characters::new("b");
+    let kept = optional(warned("tried a", 'a'));
+    assert_eq!(kept.verbose(&input), (Ok (None), vec!["tried a".to_string()]));
+    let discarded = optional(warned("tried a", 'a')).backtracking(Backtracking::Discard);
+    assert_eq!(discarded.verbose(&input), (Ok (None), Vec::new()));
+    let on_failure = optional(warned("tried a", 'a')).backtracking(Backtracking::KeepOnFailure);
+    assert_eq!(on_failure.verbose(&input), (Ok (None), Vec::new()));
+    assert_eq!(input.store_cursor(), 0);
+}
+
+#[test]
+fn choices_apply_backtracking_policies() {
+    let input: Characters = Characters::new("b");
+    let parsers = || (warned("tried a", 'a'), warned("tried b", 'b'));
+    assert_eq!(choice(parsers()).verbose(&input), (Ok ('b'), vec!["tried b".to_string()]));
+    input.move_cursor(0);
+    let kept = choice(parsers()).backtracking(Backtracking::Keep);
+    assert_eq!(kept.verbose(&input).1, ["tried a", "tried b"]);
+    input.move_cursor(0);
+    let on_failure = choice(parsers()).backtracking(Backtracking::KeepOnFailure);
+    assert_eq!(on_failure.verbose(&input).1, ["tried b"]);
+}
+
+#[test]
+fn choices_attach_abandoned_messages_to_errors_as_notes() {
+    let (failing, matching): (Characters, Characters)
+        = (Characters::new("c"), Characters::new("b"));
+    let noted = choice([warned("tried a", 'a'), warned("tried b", 'b')])
+        .attach_notes(|message: String| format!("note: {message}"));
+    assert_eq!(noted.verbose(&failing), (Err (()), vec![
+        "note: tried a".to_string(),
+        "tried b".to_string(),
+    ]));
+    assert_eq!(failing.store_cursor(), 0);
+    assert_eq!(noted.verbose(&matching), (Ok ('b'), vec!["tried b".to_string()]));
+    let listed = choice_list(vec![warned("tried a", 'a'), warned("tried c", 'c')])
+        .expect("the list is not empty")
+        .attach_notes(|message: String| format!("note: {message}"));
+    assert_eq!(listed.verbose(&matching).1, ["note: tried a", "tried c"]);
+}
+
+#[test]
+fn recovered_parsers_keep_the_messages_of_the_parser_they_recover_from() {
+    let input: Characters = Characters::new("a");
+    let parser = warned("expected b", 'b').catch(warned("recovered", 'a'));
+    assert_eq!(
+        parser.verbose(&input),
+        (Ok ('a'), vec!["expected b".to_string(), "recovered".to_string()])
+    );
+    assert_eq!(input.store_cursor(), 1);
+}
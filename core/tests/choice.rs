@@ -0,0 +1,46 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::{
+    Input,
+    prelude::*,
+};
+
+
+#[test]
+fn lists_of_parsers_choose_the_first_match() {
+    let input: Characters = Characters::new("c");
+    let parsers = [character('a'), character('b'), character('c')];
+    assert_eq!(choice_list(&parsers[..]).unwrap().parse(&input), Ok ('c'));
+    input.move_cursor(0);
+    assert_eq!(choice(parsers).parse(&input), Ok ('c'));
+    input.move_cursor(0);
+    let parsers: Vec<_> = vec![character('a'), character('b')];
+    assert_eq!(choice_list(parsers).unwrap().parse(&input), Err (()));
+    assert_eq!(input.store_cursor(), 0);
+}
+
+#[test]
+fn boxed_parsers_are_chosen_at_runtime() {
+    let input: Characters = Characters::new("b");
+    let parsers: Vec<Box<dyn Parser<char, (), (), Characters>>> = "ab".chars()
+        .map(|expected| character(expected).boxed())
+        .collect();
+    let choice = choice_dyn(parsers).unwrap();
+    assert!(choice.check(&input));
+    input.move_cursor(0);
+    assert_eq!(choice.verbose(&input), (Ok ('b'), Vec::new()));
+}
+
+#[test]
+fn empty_lists_of_parsers_are_rejected() {
+    let parsers: Vec<Box<dyn Parser<char, (), (), Characters>>> = Vec::new();
+    assert_eq!(choice_dyn(parsers).err(), Some (EmptyChoice));
+    let parsers: Vec<_> = vec![character('a')];
+    assert!(choice_list(&parsers[..0]).is_err());
+}
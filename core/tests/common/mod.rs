@@ -0,0 +1,49 @@
+// Copyright Rob Gage 2025
+
+use pups_core::Input;
+use std::cell::Cell;
+
+
+/// Input made of characters
+pub struct Characters {
+    characters: Vec<char>,
+    cursor: Cell<usize>,
+}
+
+impl Characters {
+
+    pub fn new(string: &str) -> Self {
+        Self { characters: string.chars().collect(), cursor: Cell::new(0) }
+    }
+
+}
+
+impl<'a> Input<'a> for Characters {
+
+    type Item = char;
+
+    type Slice = &'a [char];
+
+    fn advance(&self) { self.cursor.set(self.cursor.get() + 1) }
+
+    fn peek(&'a self) -> Option<char> { self.characters.get(self.cursor.get()).copied() }
+
+    fn slice(&'a self, start: usize, end: usize) -> &'a [char] { &self.characters[start..end] }
+
+    fn move_cursor(&self, position: usize) { self.cursor.set(position) }
+
+    fn store_cursor(&self) -> usize { self.cursor.get() }
+
+}
+
+
+/// Parses one expected character
+pub fn character(expected: char) -> impl for<'a> Fn(&'a Characters) -> Result<char, ()> {
+    move |input: &Characters| match input.peek() {
+        Some (found) if found == expected => {
+            input.advance();
+            Ok (found)
+        }
+        _ => Err (()),
+    }
+}
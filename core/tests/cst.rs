@@ -0,0 +1,147 @@
+// Copyright Rob Gage 2025
+
+use pups_core::{
+    Input,
+    prelude::*,
+};
+use std::cell::Cell;
+
+
+/// Input made of UTF-8 text, with cursor positions that are byte offsets
+struct Source {
+    cursor: Cell<usize>,
+    text: String,
+}
+
+impl Source {
+
+    fn new(text: &str) -> Self { Self { cursor: Cell::new(0), text: text.to_string() } }
+
+}
+
+impl<'a> Input<'a> for Source {
+
+    type Item = char;
+
+    type Slice = &'a str;
+
+    fn advance(&self) {
+        if let Some (character) = self.peek() {
+            self.cursor.set(self.cursor.get() + character.len_utf8())
+        }
+    }
+
+    fn peek(&'a self) -> Option<char> { self.text[self.cursor.get()..].chars().next() }
+
+    fn slice(&'a self, start: usize, end: usize) -> &'a str { &self.text[start..end] }
+
+    fn move_cursor(&self, position: usize) { self.cursor.set(position) }
+
+    fn store_cursor(&self) -> usize { self.cursor.get() }
+
+}
+
+
+/// Parses one or more characters that satisfy a predicate
+fn many(predicate: fn(char) -> bool) -> impl for<'a> Fn(&'a Source) -> Result<(), ()> {
+    move |input: &Source| {
+        let start: usize = input.store_cursor();
+        while let Some (character) = input.peek() && predicate(character) { input.advance() }
+        if input.store_cursor() > start { Ok (()) } else { Err (()) }
+    }
+}
+
+/// Parses an expected character
+fn symbol(expected: char) -> impl for<'a> Fn(&'a Source) -> Result<(), ()> {
+    move |input: &Source| match input.peek() {
+        Some (found) if found == expected => {
+            input.advance();
+            Ok (())
+        }
+        _ => Err (()),
+    }
+}
+
+/// Parses optional whitespace and `#` comments
+fn trivia_parser<'a>() -> impl Parser<'a, (), (), (), Source> {
+    let comment = preceded(symbol('#'), many(|character| character != '\n'))
+        .trivia("comment");
+    repeated(choice((many(char::is_whitespace), comment))).emit(())
+}
+
+/// Parses a list of names and numbers in brackets, separated by commas
+fn list_parser<'a>() -> impl Parser<'a, (), (), (), Source> {
+    let item = choice((
+        many(|character| character.is_ascii_alphabetic()).labelled("name"),
+        many(|character| character.is_ascii_digit()).labelled("number"),
+    ));
+    sequence((
+        trivia_parser(),
+        symbol('[').syntax_token("open"),
+        separated(
+            delimited(trivia_parser(), item, trivia_parser()),
+            symbol(',').syntax_token("comma"),
+        ),
+        symbol(']').syntax_token("close"),
+        trivia_parser(),
+    )).emit(()).labelled("list")
+}
+
+
+#[test]
+fn cst_reproduces_input_exactly() {
+    let text: &str = " [ apple,# fruit\n 12 , pear ]  ";
+    let input: Source = Source::new(text);
+    let (result, tree) = Cst::run(&list_parser(), &input);
+    assert!(result.is_success());
+    assert_eq!(tree.text(), text);
+    assert_eq!(tree.tokens().iter().map(SyntaxToken::text).collect::<String>(), text);
+    let kinds: Vec<&str> = tree.children_with_tokens().map(|child| child.kind()).collect();
+    assert_eq!(kinds, ["whitespace", "list", "whitespace"]);
+    let list: SyntaxNode = tree.children().next().expect("the list is a node");
+    assert_eq!((list.span().start, list.span().end), (1, 29));
+    let items: Vec<(&str, String)> = list.children()
+        .map(|node| (node.kind(), node.text()))
+        .collect();
+    assert_eq!(items, [("name", "apple".into()), ("number", "12".into()), ("name", "pear".into())]);
+    let comment: SyntaxToken = list.tokens().into_iter()
+        .find(|token| token.kind() == "comment")
+        .expect("the comment is a token");
+    assert!(comment.is_trivia());
+    assert_eq!((comment.text(), comment.parent().kind()), ("# fruit", "list"));
+}
+
+#[test]
+fn cst_leaves_out_syntax_of_abandoned_and_recovered_parsers() {
+    let input: Source = Source::new("abb;");
+    let parser = sequence((
+        lookahead(symbol('a').labelled("peeked")),
+        choice((
+            sequence((symbol('a'), symbol('x'))).emit(()).labelled("wrong"),
+            sequence((symbol('a'), symbol('b'))).emit(()).labelled("right"),
+        )),
+        sequence((symbol('b').labelled("inner"), symbol('!'))).emit(()).labelled("broken")
+            .catch(sequence((symbol('b'), symbol(';'))).emit(()).labelled("recovered")),
+    ));
+    let (result, tree) = Cst::run(&parser, &input);
+    assert!(result.is_success());
+    let kinds: Vec<&str> = tree.descendants().iter().map(SyntaxNode::kind).collect();
+    assert_eq!(kinds, ["root", "right", "recovered"]);
+    assert_eq!(tree.text(), "abb;");
+    input.move_cursor(0);
+    let (_, again) = Cst::run(&parser, &input);
+    assert_eq!(again.descendants().iter().map(SyntaxNode::kind).collect::<Vec<&str>>(), kinds);
+}
+
+#[test]
+fn cst_keeps_unparsed_input() {
+    let text: &str = "[ 1, 2 oops";
+    let input: Source = Source::new(text);
+    let (result, tree) = Cst::run(&list_parser(), &input);
+    assert!(!result.is_success());
+    assert_eq!(tree.text(), text);
+    let tokens: Vec<SyntaxToken> = tree.tokens();
+    let [unparsed] = &tokens[..] else { panic!("a failed parse leaves one token") };
+    assert_eq!((unparsed.kind(), unparsed.is_trivia()), ("unparsed", false));
+    assert_eq!(unparsed.parent(), &tree);
+}
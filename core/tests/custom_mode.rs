@@ -0,0 +1,142 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::{
+    Input,
+    Mode,
+    prelude::*,
+};
+
+
+/// A custom mode that only counts the messages produced by a parser
+struct Count;
+
+impl Mode for Count {
+
+    type OutputForm<O> = ();
+
+    type ErrorForm<E> = ();
+
+    type MessageContainer<M> = usize;
+
+    fn convert_output<O>(_: impl Into<O>) {}
+
+    fn convert_error<E>(_: impl Into<E>) {}
+
+    fn merge_outputs<OA1, OA2, OB>(_: (), _: (), _: impl Fn(OA1, OA2) -> OB) {}
+
+    fn merge_errors<EA1, EA2, EB>(_: (), _: (), _: impl Fn(EA1, EA2) -> EB) {}
+
+    fn merge_message_containers<M>(a: usize, b: impl Into<usize>) -> usize { a + b.into() }
+
+    fn map_output<OA, OB, E, M>(
+        result: ModeResult<OA, E, M, Self>,
+        _: impl Fn(OA) -> OB,
+    ) -> ModeResult<OB, E, M, Self> {
+        match result {
+            ModeResult::Success ((), count) => ModeResult::Success ((), count),
+            ModeResult::Failure ((), count) => ModeResult::Failure ((), count),
+        }
+    }
+
+    fn map_error<O, EA, EB, M>(
+        result: ModeResult<O, EA, M, Self>,
+        _: impl Fn(EA) -> EB,
+    ) -> ModeResult<O, EB, M, Self> {
+        match result {
+            ModeResult::Success ((), count) => ModeResult::Success ((), count),
+            ModeResult::Failure ((), count) => ModeResult::Failure ((), count),
+        }
+    }
+
+    fn map_messages<O, E, MA, MB>(
+        result: ModeResult<O, E, MA, Self>,
+        _: impl Fn(MA) -> MB,
+    ) -> ModeResult<O, E, MB, Self> {
+        match result {
+            ModeResult::Success ((), count) => ModeResult::Success ((), count),
+            ModeResult::Failure ((), count) => ModeResult::Failure ((), count),
+        }
+    }
+
+    fn new_message_container<M>() -> usize { 0 }
+
+    fn add_message_to_container<M>(container: &mut usize, _: M) { *container += 1 }
+
+    fn add_message_from_output<O, M>(
+        _: &(),
+        container: &mut usize,
+        _: impl FnOnce(&O) -> Option<M>,
+    ) {
+        // the output is not available in this mode, so the message is counted as if it applied
+        *container += 1
+    }
+
+}
+
+
+#[test]
+fn custom_mode_runs_generic_parsers() {
+    let input: Characters = Characters::new("ab");
+    let parser = sequence((emit_message(()), character('a'), emit_message(()), character('b')));
+    let ModeResult::Success ((), count) = parser.apply::<Count>(&input) else {
+        panic!("parser should match")
+    };
+    assert_eq!(count, 2);
+    assert_eq!(input.store_cursor(), 2);
+}
+
+#[test]
+fn custom_mode_runs_boxed_parsers() {
+    let input: Characters = Characters::new("b");
+    let parser = choice((
+        sequence((emit_message(()), character('a'))).map(|(_, a)| a).boxed(),
+        sequence((emit_message(()), character('b'), emit_message(()))).map(|(_, b, _)| b).boxed(),
+    )).backtracking(Backtracking::Keep);
+    let ModeResult::Success ((), count) = parser.apply::<Count>(&input) else {
+        panic!("parser should match")
+    };
+    assert_eq!(count, 3);
+    assert_eq!(input.store_cursor(), 1);
+}
+
+#[test]
+fn custom_mode_reports_failures_through_boxed_parsers() {
+    let input: Characters = Characters::new("c");
+    let parser = sequence((emit_message(()), character('a'))).boxed();
+    let ModeResult::Failure ((), count) = Count::apply_parser(&*parser, &input) else {
+        panic!("parser should not match")
+    };
+    assert_eq!(count, 1);
+    assert_eq!(input.store_cursor(), 0);
+}
+
+#[test]
+fn observing_modes_see_parsers_nested_in_boxed_parsers() {
+    let input: Characters = Characters::new("ab");
+    let parser = sequence((character('a'), character('b'))).boxed();
+    let (result, trace) = Trace::run(&parser, &input);
+    assert!(result.is_success());
+    assert_eq!(trace.name, "Box");
+    let ends: Vec<usize> = trace.children.iter().map(|character| character.end).collect();
+    assert_eq!(ends, [1, 2]);
+}
+
+#[test]
+fn custom_mode_keeps_messages_of_rejected_outputs() {
+    let input: Characters = Characters::new("a");
+    let parser = try_map(
+        sequence((emit_message(()), character('a'))),
+        |_, _| Err::<char, ()> (()),
+    );
+    let ModeResult::Failure ((), count) = parser.apply::<Count>(&input) else {
+        panic!("parser should not match")
+    };
+    assert_eq!(count, 1);
+    assert_eq!(input.store_cursor(), 0);
+}
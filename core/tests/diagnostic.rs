@@ -0,0 +1,78 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::prelude::*;
+
+
+#[test]
+fn diagnostics_are_sorted_by_position_without_duplicates() {
+    let diagnostics: Diagnostics = [
+        Diagnostic::warning(Span::new(4, 5), "unused"),
+        Diagnostic::error(Span::new(0, 1), "unexpected"),
+        Diagnostic::warning(Span::new(4, 5), "unused"),
+        Diagnostic::note(Span::new(2, 3), "declared here"),
+    ].into_iter().collect();
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!((diagnostics.count(Severity::Warning), diagnostics.count(Severity::Help)), (1, 0));
+    assert!(diagnostics.has_errors());
+    let starts: Vec<usize> = diagnostics.iter().map(|diagnostic| diagnostic.span.start).collect();
+    assert_eq!(starts, [0, 2, 4]);
+    assert_eq!(diagnostics.into_vec()[2].message, "unused");
+}
+
+#[test]
+fn limits_keep_the_first_diagnostics_of_each_severity() {
+    let mut diagnostics: Diagnostics = Diagnostics::new().with_limit(Severity::Warning, 2);
+    for position in [6, 2, 4, 0] {
+        diagnostics.push(Diagnostic::warning(Span::new(position, position), "warning"));
+        diagnostics.push(Diagnostic::error(Span::new(position, position), "error"));
+    }
+    assert_eq!(diagnostics.count(Severity::Warning), 2);
+    assert_eq!(diagnostics.count(Severity::Error), 4);
+    assert_eq!(diagnostics.omitted(Severity::Warning), 2);
+    assert_eq!(diagnostics.omitted(Severity::Error), 0);
+    let warnings: Vec<usize> = diagnostics.iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Warning)
+        .map(|diagnostic| diagnostic.span.start)
+        .collect();
+    assert_eq!(warnings, [0, 2]);
+}
+
+#[test]
+fn verbose_parsers_collect_messages_into_diagnostics() {
+    let input: Characters = Characters::new("a,a,a,");
+    let letters = separated(
+        character('a').map_messages(|()| unreachable!("characters produce no messages")),
+        character(',').map_messages(|()| unreachable!("characters produce no messages")),
+    ).reported().map_messages(|message: IterationMessage| -> Diagnostic { message.into() });
+    let parser = sequence((letters, emit_message(Diagnostic::note(Span::new(0, 0), "started"))));
+    let limits: Diagnostics = Diagnostics::new().with_limit(Severity::Note, 0);
+    let (result, diagnostics) = Verbose::diagnose(&parser, &input, limits);
+    assert_eq!(result.map(|(letters, ())| letters.len()), Ok (3));
+    let messages: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+    assert_eq!(messages, ["error [5..5]: trailing separator is not allowed"]);
+    assert_eq!(diagnostics.omitted(Severity::Note), 1);
+}
+
+#[test]
+fn duplicates_of_omitted_diagnostics_are_counted_once() {
+    let mut diagnostics: Diagnostics = Diagnostics::new()
+        .with_limit(Severity::Note, 0)
+        .with_limit(Severity::Warning, 1);
+    for _ in 0..2 {
+        diagnostics.push(Diagnostic::note(Span::new(0, 1), "noted"));
+        diagnostics.push(Diagnostic::warning(Span::new(4, 5), "later"));
+        diagnostics.push(Diagnostic::warning(Span::new(2, 3), "earlier"));
+    }
+    assert_eq!((diagnostics.count(Severity::Note), diagnostics.omitted(Severity::Note)), (0, 1));
+    assert_eq!(
+        (diagnostics.count(Severity::Warning), diagnostics.omitted(Severity::Warning)),
+        (1, 1)
+    );
+    assert_eq!(diagnostics.into_vec()[0].message, "earlier");
+}
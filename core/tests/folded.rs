@@ -0,0 +1,79 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::{
+    Input,
+    prelude::*,
+};
+use std::cell::RefCell;
+
+
+/// Parses a digit from 1 to 3 as a string
+fn operand<'a>() -> impl Parser<'a, String, (), (), Characters> {
+    choice((character('1'), character('2'), character('3'))).map(|digit| digit.to_string())
+}
+
+/// Combines two operands with an operator into a parenthesized string
+fn parenthesize(left: String, operator: char, right: String) -> String {
+    format!("({left}{operator}{right})")
+}
+
+
+#[test]
+fn folds_group_operands_by_associativity() {
+    let input: Characters = Characters::new("1-2-3");
+    let left = foldl(operand(), character('-'), parenthesize);
+    assert_eq!(left.parse(&input), Ok ("((1-2)-3)".to_string()));
+    let (chain, single): (Characters, Characters)
+        = (Characters::new("1^2^3"), Characters::new("3"));
+    let right = foldr(operand(), character('^'), parenthesize);
+    assert_eq!(right.parse(&chain), Ok ("(1^(2^3))".to_string()));
+    assert_eq!(right.parse(&single), Ok ("3".to_string()));
+}
+
+#[test]
+fn folds_leave_trailing_operators_unconsumed() {
+    let input: Characters = Characters::new("1-2-");
+    let left = foldl(operand(), character('-'), parenthesize);
+    assert_eq!(left.parse(&input), Ok ("(1-2)".to_string()));
+    assert_eq!(input.store_cursor(), 3);
+    input.move_cursor(0);
+    assert!(left.check(&input));
+    assert_eq!(input.store_cursor(), 3);
+}
+
+#[test]
+fn folds_stop_when_operations_consume_no_input() {
+    let input: Characters = Characters::new("1");
+    let operand = character('1').or_not().map(|digit| digit.map_or(0, |_| 1));
+    let sum = foldl(operand, nothing::<(), (), Characters>(), |left, (), right| left + right);
+    assert_eq!(sum.parse(&input), Ok (1));
+    assert_eq!(input.store_cursor(), 1);
+}
+
+#[test]
+fn left_folds_combine_each_operation_as_it_is_parsed() {
+    let input: Characters = Characters::new("1-2-3");
+    let steps: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    let operand = |input: &Characters| {
+        steps.borrow_mut().push(format!("operand at {}", input.store_cursor()));
+        operand().parse(input)
+    };
+    let left = foldl(operand, character('-'), |left, operator, right| {
+        steps.borrow_mut().push(format!("fold {right}"));
+        parenthesize(left, operator, right)
+    });
+    assert_eq!(left.parse(&input), Ok ("((1-2)-3)".to_string()));
+    assert_eq!(*steps.borrow(), [
+        "operand at 0",
+        "operand at 2",
+        "fold 2",
+        "operand at 4",
+        "fold 3",
+    ]);
+}
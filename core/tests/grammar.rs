@@ -0,0 +1,107 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::{
+    Mode,
+    prelude::*,
+};
+
+
+/// Parses one expected character, describing itself as a terminal
+struct Letter (char);
+
+impl<'a> Parser<'a, char, (), (), Characters> for Letter {
+
+    fn apply<_Mode: Mode>(&self, input: &'a Characters) -> ModeResult<char, (), (), _Mode> {
+        _Mode::apply_parser(self, input)
+    }
+
+    fn check(&self, input: &'a Characters) -> bool { self.parse(input).is_ok() }
+
+    fn parse(&self, input: &'a Characters) -> Result<char, ()> { character(self.0)(input) }
+
+    fn verbose(&self, input: &'a Characters) -> (Result<char, ()>, Vec<()>) {
+        (self.parse(input), Vec::new())
+    }
+
+    fn describe(&self) -> Grammar { Grammar::terminal(self.0) }
+
+}
+
+
+#[test]
+fn labelled_parsers_become_rules() {
+    let input: Characters = Characters::new("10,1");
+    let digit = || choice((Letter ('0'), Letter ('1'))).labelled("digit");
+    let number = sequenced(digit(), repeated(digit())).labelled("number");
+    let list = separated(number, Letter (',')).labelled("list");
+    let grammar: Grammar = Parser::<_, (), (), Characters>::describe(&list);
+    assert_eq!(
+        grammar.to_ebnf(),
+        "list = [ number , { \",\" , number } ] ;\n\
+         number = digit , { digit } ;\n\
+         digit = \"0\" | \"1\" ;\n"
+    );
+    assert!(list.check(&input));
+}
+
+#[test]
+fn ebnf_groups_and_expands_repetitions() {
+    let parser = sequence((
+        choice((Letter ('a'), Letter ('b'))).or_not(),
+        repeated(Letter ('c')).at_least(2).at_most(3),
+        not(Letter ('d'), |_| ()),
+        character('e'),
+    ));
+    let grammar: Grammar = Parser::<_, _, (), Characters>::describe(&parser);
+    assert_eq!(
+        grammar.to_ebnf(),
+        "grammar = [ \"a\" | \"b\" ] , ( \"c\" , \"c\" , [ \"c\" ] ) , \
+         ? not followed by \"d\" ? , ? unknown ? ;\n"
+    );
+}
+
+#[test]
+fn ebnf_splits_terminals_that_contain_both_quotes() {
+    let grammar: Grammar = Grammar::sequence([
+        Grammar::terminal("say \"it's\""),
+        Grammar::choice([Grammar::terminal("'\""), Grammar::terminal("'")]),
+    ]);
+    assert_eq!(
+        grammar.to_ebnf(),
+        "grammar = ( 'say \"it' , \"'s\" , '\"' ) , ( \"'\" , '\"' | \"'\" ) ;\n"
+    );
+}
+
+#[test]
+fn railroad_diagram_draws_every_rule() {
+    let word = repeated(Letter ('w')).at_least(1).labelled("word");
+    let phrase = separated(word, Letter (' ')).at_least(1).labelled("phrase");
+    let grammar: Grammar = Parser::<_, _, (), Characters>::describe(&phrase);
+    let svg: String = grammar.to_railroad_svg();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>"));
+    assert!(svg.contains(">phrase</text>"));
+    assert!(svg.contains(">word</text>"));
+    assert!(svg.contains("<text class=\"terminal\"") && svg.contains(">w</text>"));
+}
+
+#[test]
+fn recursive_rules_refer_to_themselves() {
+    // describes `nested = "(" , [ nested ] , ")"` the way a recursive parser would
+    fn nested() -> Grammar {
+        Grammar::rule("nested", || Grammar::sequence([
+            Grammar::terminal("("),
+            Grammar::optional(nested()),
+            Grammar::terminal(")"),
+        ]))
+    }
+    let grammar: Grammar = nested();
+    assert_eq!(grammar.to_ebnf(), "nested = \"(\" , [ nested ] , \")\" ;\n");
+    assert_eq!(grammar.rules().len(), 1);
+}
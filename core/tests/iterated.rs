@@ -0,0 +1,90 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::{
+    Input,
+    prelude::*,
+};
+
+
+/// Parses one expected character, with `IterationMessage`s as messages
+fn item<'a>(expected: char) -> impl Parser<'a, char, (), IterationMessage, Characters> {
+    character(expected).map_messages(|()| unreachable!("characters produce no messages"))
+}
+
+
+#[test]
+fn only_trailing_separators_that_match_input_are_reported() {
+    let input: Characters = Characters::new("aa,");
+    let repeated = repeated(item('a')).reported();
+    let (result, messages): (Result<Vec<char>, ()>, Vec<IterationMessage>)
+        = repeated.verbose(&input);
+    assert_eq!((result.map(|items| items.len()), messages), (Ok (2), Vec::new()));
+    let input: Characters = Characters::new("a,a,b");
+    let separated = separated(item('a'), item(',')).reported();
+    let (result, messages): (Result<Vec<char>, ()>, Vec<IterationMessage>)
+        = separated.verbose(&input);
+    assert_eq!(
+        (result.map(|items| items.len()), messages),
+        (Ok (2), vec![IterationMessage::TrailingSeparator { position: 3 }])
+    );
+    assert_eq!(input.store_cursor(), 3);
+}
+
+#[test]
+fn iteration_messages_are_only_produced_when_reported() {
+    let input: Characters = Characters::new("a,a,");
+    let separated = separated(item('a'), item(','));
+    assert_eq!(separated.verbose(&input), (Ok (vec!['a', 'a']), Vec::new()));
+    assert_eq!(input.store_cursor(), 3);
+}
+
+#[test]
+fn separator_policies_control_leading_and_trailing_separators() {
+    let input: Characters = Characters::new(",a,a,");
+    let forbidden = separated(character('a'), character(','));
+    assert_eq!(forbidden.parse(&input), Ok (Vec::new()));
+    assert_eq!(input.store_cursor(), 0);
+    let allowed = separated(character('a'), character(',')).allow_leading().allow_trailing();
+    assert_eq!(allowed.parse(&input), Ok (vec!['a', 'a']));
+    assert_eq!(input.store_cursor(), 5);
+    input.move_cursor(1);
+    let required = separated(character('a'), character(',')).require_trailing();
+    assert_eq!(required.parse(&input), Ok (vec!['a', 'a']));
+    assert_eq!(input.store_cursor(), 5);
+    let input: Characters = Characters::new("a,a");
+    assert_eq!(required.parse(&input), Err (()));
+    assert_eq!(input.store_cursor(), 0);
+}
+
+#[test]
+fn iterations_that_consume_no_input_stop_iteration() {
+    let input: Characters = Characters::new("aab");
+    let repeated = repeated(item('a').or_not()).reported();
+    let (result, messages): (Result<Vec<Option<char>>, ()>, Vec<IterationMessage>)
+        = repeated.verbose(&input);
+    assert_eq!(result, Ok (vec![Some ('a'), Some ('a')]));
+    let [IterationMessage::Stalled { parser, position }] = messages[..] else {
+        panic!("iteration should stall once: {messages:?}")
+    };
+    assert!(parser.contains("Optional"), "{parser}");
+    assert_eq!(position, 2);
+    assert_eq!(input.store_cursor(), 2);
+    input.move_cursor(0);
+    assert!(repeated.check(&input));
+    assert_eq!(input.store_cursor(), 2);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "succeeded without consuming input at position 1")]
+fn asserting_progress_panics_when_iterations_consume_no_input() {
+    let input: Characters = Characters::new("ab");
+    let repeated = repeated(character('a').or_not()).assert_progress();
+    let _ = repeated.parse(&input);
+}
@@ -0,0 +1,56 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::{
+    Input,
+    prelude::*,
+};
+use std::cell::Cell;
+
+
+#[test]
+fn lookahead_restores_the_cursor() {
+    let input: Characters = Characters::new("ab");
+    let parser = lookahead(sequence((character('a'), character('b'))));
+    assert_eq!(parser.parse(&input), Ok (('a', 'b')));
+    assert_eq!(input.store_cursor(), 0);
+    assert_eq!(character('b').rewind().parse(&input), Err (()));
+    assert_eq!(input.store_cursor(), 0);
+    assert!(character('a').rewind().check(&input));
+    assert_eq!(input.store_cursor(), 0);
+}
+
+#[test]
+fn negation_fails_with_an_error_for_the_matched_span() {
+    let input: Characters = Characters::new("ab");
+    let ab = sequence((character('a'), character('b'))).map_error(|()| Span::new(0, 0));
+    let parser = not(ab, |span| span);
+    assert_eq!(parser.parse(&input), Err (Span::new(0, 2)));
+    assert_eq!(input.store_cursor(), 0);
+    assert_eq!(not(character('b'), |_| ()).parse(&input), Ok (()));
+    assert_eq!(input.store_cursor(), 0);
+    let letter = character('a').and_is_not(character('a'), |_| ());
+    assert!(!letter.check(&input));
+    let letter = character('a').and_is_not(character('b'), |_| ());
+    assert_eq!(letter.parse(&input), Ok ('a'));
+    assert_eq!(input.store_cursor(), 1);
+}
+
+#[test]
+fn negated_parsers_do_not_build_outputs() {
+    let input: Characters = Characters::new("a");
+    let mapped: Cell<usize> = Cell::new(0);
+    let counted = character('a').map(|letter| {
+        mapped.set(mapped.get() + 1);
+        letter
+    });
+    let parser = not(counted, |_| ());
+    assert_eq!(parser.parse(&input), Err (()));
+    assert_eq!(parser.verbose(&input), (Err (()), Vec::new()));
+    assert_eq!(mapped.get(), 0);
+}
@@ -0,0 +1,77 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::{
+    Input,
+    prelude::*,
+};
+
+
+/// Parses one expected character, with `PermutationMessage`s as messages
+fn member<'a>(expected: char) -> impl Parser<'a, char, (), PermutationMessage, Characters> {
+    character(expected).map_messages(|()| unreachable!("characters produce no messages"))
+}
+
+
+#[test]
+fn permutations_match_members_in_any_order() {
+    let input: Characters = Characters::new("cab");
+    let parser = permutation((character('a'), character('b'), character('c')));
+    assert_eq!(parser.parse(&input), Ok (('a', 'b', 'c')));
+    assert_eq!(input.store_cursor(), 3);
+    let input: Characters = Characters::new("b");
+    let parser = permutation((character('a').or_not(), character('b')));
+    assert_eq!(parser.parse(&input), Ok ((None, 'b')));
+}
+
+#[test]
+fn permutations_with_missing_members_fail() {
+    let input: Characters = Characters::new("ba");
+    let parser = permutation((character('a'), character('b'), character('c')));
+    assert_eq!(parser.verbose(&input), (Err (()), Vec::new()));
+    assert_eq!(input.store_cursor(), 0);
+}
+
+#[test]
+fn reported_permutations_report_missing_and_duplicated_members() {
+    let (invalid, valid): (Characters, Characters)
+        = (Characters::new("aba"), Characters::new("bca"));
+    let parser = permutation((member('a'), member('b'), member('c'))).reported();
+    assert_eq!(parser.verbose(&invalid), (Err (()), vec![
+        PermutationMessage::Duplicated { member: 0, position: 2 },
+        PermutationMessage::Missing { member: 2, position: 2 },
+    ]));
+    assert_eq!(invalid.store_cursor(), 0);
+    assert!(!parser.check(&invalid));
+    assert_eq!(parser.verbose(&valid), (Ok (('a', 'b', 'c')), Vec::new()));
+}
+
+#[test]
+fn failed_permutations_keep_the_messages_of_every_member() {
+    let input: Characters = Characters::new("a");
+    let warned = |message: &'static str, expected: char| sequence((
+        emit_message(message),
+        character(expected).map_messages(|()| unreachable!("characters produce no messages")),
+    )).map(|((), character)| character);
+    let parser = permutation((warned("saw a", 'a'), warned("saw b", 'b')));
+    assert_eq!(parser.verbose(&input), (Err (()), vec!["saw a", "saw b"]));
+}
+
+#[test]
+fn duplicate_probes_are_observed_without_consuming_input() {
+    let input: Characters = Characters::new("aba");
+    let parser = permutation((member('a'), member('b'))).reported();
+    let (result, trace) = Trace::run(&parser, &input);
+    assert!(result.is_success());
+    assert_eq!(trace.end, 2);
+    let probes: Vec<(usize, usize)> = trace.children.iter()
+        .filter(|child| child.name == "Probe")
+        .map(|child| (child.start, child.end))
+        .collect();
+    assert_eq!(probes, [(2, 2), (2, 2)]);
+}
@@ -0,0 +1,44 @@
+// Copyright Rob Gage 2025
+
+use pups_core::prelude::*;
+
+
+/// Creates a document of a call with arguments that are laid out on one line if they fit
+fn call(name: &str, arguments: Vec<Doc>) -> Doc {
+    let mut listed: Vec<Doc> = Vec::new();
+    for (index, argument) in arguments.into_iter().enumerate() {
+        if index > 0 { listed.extend([Doc::text(","), Doc::line()]) }
+        listed.push(argument);
+    }
+    Doc::group(Doc::concat([
+        Doc::text(format!("{name}(")),
+        Doc::nest(4, Doc::soft_line().append(Doc::concat(listed))),
+        Doc::soft_line(),
+        Doc::text(")"),
+    ]))
+}
+
+
+#[test]
+fn groups_break_only_when_they_do_not_fit() {
+    let doc: Doc = call("f", vec![Doc::text("alpha"), Doc::text("beta")]);
+    assert_eq!(doc.render(80), "f(alpha, beta)");
+    assert_eq!(doc.render(10), "f(\n    alpha,\n    beta\n)");
+}
+
+#[test]
+fn nested_groups_fit_after_their_parent_breaks() {
+    let inner: Doc = call("g", vec![Doc::text("1"), Doc::text("2")]);
+    let doc: Doc = call("f", vec![Doc::text("alpha"), inner]);
+    assert_eq!(doc.render(80), "f(alpha, g(1, 2))");
+    assert_eq!(doc.render(12), "f(\n    alpha,\n    g(1, 2)\n)");
+    assert_eq!(doc.render(6), "f(\n    alpha,\n    g(\n        1,\n        2\n    )\n)");
+}
+
+#[test]
+fn hard_lines_break_their_groups() {
+    let doc: Doc = call("f", vec![Doc::text("a\nb"), Doc::text("c")]);
+    assert_eq!(doc.render(80), "f(\n    a\n    b,\n    c\n)");
+    let blank: Doc = Doc::nest(4, Doc::concat([Doc::text("a"), Doc::HardLine, Doc::HardLine]));
+    assert_eq!(blank.render(80), "a\n\n    ");
+}
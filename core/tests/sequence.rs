@@ -0,0 +1,52 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::{
+    Input,
+    prelude::*,
+};
+
+
+#[test]
+fn sequences_output_flat_tuples() {
+    let input: Characters = Characters::new("abc");
+    assert_eq!(sequence((character('a'),)).parse(&input), Ok (('a',)));
+    let input: Characters = Characters::new("abc");
+    let parser = sequence((character('a'), character('b'), character('c')));
+    assert_eq!(parser.parse(&input), Ok (('a', 'b', 'c')));
+    assert_eq!(input.store_cursor(), 3);
+    let input: Characters = Characters::new("abcd");
+    let parser = sequence((
+        character('a'),
+        character('b'),
+        character('c').or_not(),
+        character('x').or_not(),
+        character('d'),
+    ));
+    assert_eq!(parser.parse(&input), Ok (('a', 'b', Some ('c'), None, 'd')));
+}
+
+#[test]
+fn failed_sequences_restore_the_cursor() {
+    let input: Characters = Characters::new("abx");
+    let parser = sequence((character('a'), character('b'), character('c')));
+    assert_eq!(parser.parse(&input), Err (()));
+    assert_eq!(input.store_cursor(), 0);
+    assert!(!parser.check(&input));
+    assert_eq!(input.store_cursor(), 0);
+    assert_eq!(parser.verbose(&input), (Err (()), Vec::new()));
+    assert_eq!(input.store_cursor(), 0);
+}
+
+#[test]
+fn sequences_collect_messages_from_every_member() {
+    let input: Characters = Characters::new("ab");
+    let parser = sequence((emit_message(()), character('a'), emit_message(()), character('b')))
+        .map(|(_, a, _, b)| (a, b));
+    assert_eq!(parser.verbose(&input), (Ok (('a', 'b')), vec![(), ()]));
+}
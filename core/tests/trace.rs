@@ -0,0 +1,70 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::{
+    Input,
+    prelude::*,
+};
+
+
+#[test]
+fn trace_records_every_invocation() {
+    let input: Characters = Characters::new("ab");
+    let parser = sequence((
+        character('a').trace("a"),
+        choice((character('x'), character('b'))),
+    ));
+    let (result, trace) = Trace::run(&parser, &input);
+    let ModeResult::Success ((a, b), _) = result else { panic!("parser should match") };
+    assert_eq!((a, b), ('a', 'b'));
+    assert_eq!((trace.name, trace.start, trace.end, trace.success), ("Sequence", 0, 2, true));
+    assert_eq!(trace.size(), 5);
+    let [traced, chosen] = &trace.children[..] else { panic!("sequence has two members") };
+    assert_eq!((traced.name, traced.end, traced.success), ("a", 1, true));
+    assert!(traced.children.is_empty());
+    assert_eq!(chosen.name, "Choice");
+    let successes: Vec<bool> = chosen.children.iter().map(|node| node.success).collect();
+    assert_eq!(successes, [false, true]);
+}
+
+#[test]
+fn trace_records_recovered_and_rejected_invocations() {
+    let input: Characters = Characters::new("a");
+    let recovered = character('b').trace("primary").catch(character('a').trace("fallback"));
+    let (result, trace) = Trace::run(&recovered, &input);
+    let ModeResult::Success (output, _) = result else { panic!("the fallback should match") };
+    assert_eq!(output, 'a');
+    let attempts: Vec<(&str, usize, bool)> = trace.children.iter()
+        .map(|node| (node.name, node.end, node.success))
+        .collect();
+    assert_eq!(attempts, [("primary", 0, false), ("fallback", 1, true)]);
+    input.move_cursor(0);
+    let rejected = filter(character('a').trace("letter"), "the letter b", |letter| *letter == 'b');
+    let (result, trace) = Trace::run(&rejected, &input);
+    assert!(!result.is_success());
+    assert_eq!(input.store_cursor(), 0);
+    assert_eq!((trace.end, trace.success), (0, false));
+    let [letter] = &trace.children[..] else { panic!("the letter is applied once") };
+    assert_eq!((letter.name, letter.end, letter.success), ("letter", 1, true));
+}
+
+#[test]
+fn trace_dumps_text_and_json() {
+    let input: Characters = Characters::new("a");
+    let parser = sequence((character('a').trace("letter"),)).trace("word");
+    let (_, trace) = Trace::run(&parser, &input);
+    let text: String = trace.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("word [0..1] matched in "));
+    assert!(lines[1].starts_with("  letter [0..1] matched in "));
+    let json: String = trace.to_json();
+    assert!(json.starts_with("{\"name\":\"word\",\"start\":0,\"end\":1,\"success\":true,"));
+    assert!(json.contains("\"children\":[{\"name\":\"letter\",\"start\":0,\"end\":1,"));
+    assert!(json.ends_with("\"children\":[]}]}"));
+}
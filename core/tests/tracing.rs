@@ -0,0 +1,131 @@
+// Copyright Rob Gage 2025
+
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::prelude::*;
+use std::{
+    fmt::Debug,
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+use tracing::{
+    field::{
+        Field,
+        Visit,
+    },
+    span::{
+        Attributes,
+        Id,
+        Record,
+    },
+    subscriber::with_default,
+    Event,
+    Metadata,
+    Subscriber,
+};
+
+
+/// The fields recorded for one span
+#[derive(Debug, Default, PartialEq)]
+struct Recorded {
+    /// The name of the parser
+    name: String,
+    /// The cursor position before the parser was applied
+    start: u64,
+    /// The cursor position after the parser was applied
+    end: u64,
+    /// Whether the parser matched
+    outcome: String,
+}
+
+impl Visit for Recorded {
+
+    fn record_debug(&mut self, _: &Field, _: &dyn Debug) {}
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "name" => self.name = value.to_string(),
+            "outcome" => self.outcome = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "start" => self.start = value,
+            "end" => self.end = value,
+            _ => {}
+        }
+    }
+
+}
+
+/// A subscriber that records the fields of every span
+#[derive(Clone, Default)]
+struct Recorder (Arc<Mutex<Vec<Recorded>>>);
+
+impl Subscriber for Recorder {
+
+    fn enabled(&self, _: &Metadata) -> bool { true }
+
+    fn new_span(&self, attributes: &Attributes) -> Id {
+        let mut spans = self.0.lock().unwrap();
+        let mut recorded: Recorded = Recorded::default();
+        attributes.record(&mut recorded);
+        spans.push(recorded);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record) {
+        values.record(&mut self.0.lock().unwrap()[span.into_u64() as usize - 1]);
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+
+}
+
+
+#[test]
+fn traced_and_labelled_parsers_open_spans() {
+    let input: Characters = Characters::new("ab");
+    let parser = sequence((
+        character('a').trace("letter"),
+        labelled(character('c'), "other"),
+    ));
+    let recorder: Recorder = Recorder::default();
+    let result: Result<(char, char), ()> = with_default(recorder.clone(), || parser.parse(&input));
+    assert_eq!(result, Err (()));
+    let recorded: &[Recorded] = &recorder.0.lock().unwrap();
+    assert_eq!(recorded, [
+        Recorded { name: "letter".into(), start: 0, end: 1, outcome: "matched".into() },
+        Recorded { name: "other".into(), start: 1, end: 1, outcome: "failed".into() },
+    ]);
+}
+
+#[test]
+fn recovered_parsers_report_the_failed_attempt_and_the_fallback() {
+    let input: Characters = Characters::new("a");
+    let parser = character('b').trace("primary").catch(character('a').trace("fallback"));
+    let recorder: Recorder = Recorder::default();
+    let result: Result<char, ()> = with_default(recorder.clone(), || parser.parse(&input));
+    assert_eq!(result, Ok ('a'));
+    let recorded: &[Recorded] = &recorder.0.lock().unwrap();
+    assert_eq!(recorded, [
+        Recorded { name: "primary".into(), start: 0, end: 0, outcome: "failed".into() },
+        Recorded { name: "fallback".into(), start: 0, end: 1, outcome: "matched".into() },
+    ]);
+}
@@ -0,0 +1,68 @@
+// Copyright Rob Gage 2025
+
+mod common;
+
+use common::{
+    character,
+    Characters,
+};
+use pups_core::{
+    Input,
+    prelude::*,
+};
+
+
+/// Parses one expected character, with string slices as messages
+fn letter<'a>(expected: char) -> impl Parser<'a, char, (), &'static str, Characters> {
+    character(expected).map_messages(|()| unreachable!("characters produce no messages"))
+}
+
+
+#[test]
+fn rejected_outputs_fail_and_restore_the_cursor_in_every_mode() {
+    let input: Characters = Characters::new("ab");
+    let pair = sequence((character('a'), character('b'))).map_error(|()| Span::new(0, 0));
+    let rejected = try_map(pair, |_, span| Err::<char, Span> (span));
+    assert_eq!(rejected.parse(&input), Err (Span::new(0, 2)));
+    assert_eq!(input.store_cursor(), 0);
+    assert!(!rejected.check(&input));
+    assert_eq!(input.store_cursor(), 0);
+    assert_eq!(rejected.verbose(&input), (Err (Span::new(0, 2)), Vec::new()));
+    assert_eq!(input.store_cursor(), 0);
+    let accepted = filter(character('a'), "the letter a", |letter| *letter == 'a');
+    assert!(accepted.check(&input));
+    assert_eq!(input.store_cursor(), 1);
+    input.move_cursor(0);
+    let vowel = filter(character('a').or_not(), "a vowel", Option::is_none);
+    assert!(!vowel.check(&input));
+    assert_eq!(input.store_cursor(), 0);
+}
+
+#[test]
+fn validated_outputs_emit_messages_only_in_verbose_modes() {
+    let input: Characters = Characters::new("a");
+    let parser = validate(letter('a'), |letter, span, emitter| {
+        emitter.emit("validated");
+        (letter, span)
+    });
+    assert_eq!(parser.parse(&input), Ok (('a', Span::new(0, 1))));
+    input.move_cursor(0);
+    assert!(parser.check(&input));
+    input.move_cursor(0);
+    assert_eq!(parser.verbose(&input), (Ok (('a', Span::new(0, 1))), vec!["validated"]));
+}
+
+#[test]
+fn parsers_nested_in_validation_are_traced() {
+    let input: Characters = Characters::new("ab");
+    let parser = filter(
+        sequence((character('a'), character('b'))),
+        "a pair",
+        |(a, b)| a != b,
+    );
+    let (result, trace) = Trace::run(&parser, &input);
+    assert!(result.is_success());
+    assert_eq!((trace.name, trace.end), ("TryMapped", 2));
+    let [sequence] = &trace.children[..] else { panic!("the filtered parser should be traced") };
+    assert_eq!((sequence.name, sequence.children.len()), ("Sequence", 2));
+}
@@ -0,0 +1,122 @@
+// Copyright Rob Gage 2025
+
+use syn::{
+    Attribute,
+    Expr,
+    LitInt,
+    LitStr,
+    meta::ParseNestedMeta,
+    Result,
+};
+
+
+/// The `#[pups(...)]` attributes of a struct, enum, or enum variant
+#[derive(Default)]
+pub struct TypeAttributes {
+    /// Whether whitespace is left to the parsers instead of being skipped before each element
+    pub no_whitespace: bool,
+    /// The tokens that come before the fields
+    pub prefixes: Vec<LitStr>,
+    /// The tokens that come after the fields
+    pub suffixes: Vec<LitStr>,
+}
+
+impl TypeAttributes {
+
+    /// Reads the `#[pups(...)]` attributes of a struct, enum, or enum variant
+    pub fn read(attributes: &[Attribute]) -> Result<Self> {
+        let mut read: Self = Self::default();
+        for attribute in attributes.iter().filter(|attribute| attribute.path().is_ident("pups")) {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("no_whitespace") {
+                    read.no_whitespace = true;
+                } else if meta.path.is_ident("prefix") {
+                    read.prefixes.push(meta.value()?.parse()?);
+                } else if meta.path.is_ident("suffix") {
+                    read.suffixes.push(meta.value()?.parse()?);
+                } else {
+                    return Err (unknown(&meta))
+                }
+                Ok (())
+            })?;
+        }
+        Ok (read)
+    }
+
+}
+
+
+/// How the value of a field, or of each item of a field, is parsed
+pub enum Leaf {
+    /// With `unicode_identifier`, converting the identifier with `From<&str>`
+    Identifier,
+    /// With `number`, converting the number with `FromStr`
+    Number,
+    /// With the `Parsable` implementation of its type
+    Parsable,
+    /// With a parser expression
+    Parser (Expr),
+}
+
+/// The `#[pups(...)]` attributes of a field
+pub struct FieldAttributes {
+    /// How the value of the field, or of each of its items, is parsed
+    pub leaf: Leaf,
+    /// The minimum number of items of a `Vec` field
+    pub minimum: Option<LitInt>,
+    /// The tokens that come before the field
+    pub prefixes: Vec<LitStr>,
+    /// The token in between the items of a `Vec` field
+    pub separator: Option<LitStr>,
+    /// The tokens that come after the field
+    pub suffixes: Vec<LitStr>,
+    /// Whether a `Vec` field may end with a separator
+    pub trailing: bool,
+}
+
+impl FieldAttributes {
+
+    /// Reads the `#[pups(...)]` attributes of a field
+    pub fn read(attributes: &[Attribute]) -> Result<Self> {
+        let mut read: Self = Self {
+            leaf: Leaf::Parsable,
+            minimum: None,
+            prefixes: Vec::new(),
+            separator: None,
+            suffixes: Vec::new(),
+            trailing: false,
+        };
+        for attribute in attributes.iter().filter(|attribute| attribute.path().is_ident("pups")) {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("at_least") {
+                    read.minimum = Some (meta.value()?.parse()?);
+                } else if meta.path.is_ident("identifier") {
+                    read.leaf = Leaf::Identifier;
+                } else if meta.path.is_ident("number") {
+                    read.leaf = Leaf::Number;
+                } else if meta.path.is_ident("parser") {
+                    read.leaf = Leaf::Parser (meta.value()?.parse()?);
+                } else if meta.path.is_ident("prefix") {
+                    read.prefixes.push(meta.value()?.parse()?);
+                } else if meta.path.is_ident("separator") {
+                    read.separator = Some (meta.value()?.parse()?);
+                } else if meta.path.is_ident("suffix") {
+                    read.suffixes.push(meta.value()?.parse()?);
+                } else if meta.path.is_ident("trailing") {
+                    read.trailing = true;
+                } else {
+                    return Err (unknown(&meta))
+                }
+                Ok (())
+            })?;
+        }
+        Ok (read)
+    }
+
+}
+
+
+/// The error for an attribute that is not recognised
+fn unknown(meta: &ParseNestedMeta) -> syn::Error {
+    meta.error("unsupported `pups` attribute")
+}
@@ -0,0 +1,438 @@
+// Copyright Rob Gage 2025
+
+use proc_macro2::{
+    Span,
+    TokenStream as Tokens,
+};
+use quote::{
+    format_ident,
+    quote,
+    quote_spanned,
+};
+use syn::{
+    Attribute,
+    Block,
+    Ident,
+    LitStr,
+    parenthesized,
+    parse::{
+        Parse,
+        ParseStream,
+    },
+    Result,
+    token,
+    Token,
+    Type,
+    Visibility,
+};
+
+
+/// The rules of a grammar
+pub struct Rules (Vec<Rule>);
+
+impl Parse for Rules {
+
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut rules: Vec<Rule> = Vec::new();
+        while !input.is_empty() { rules.push(input.parse()?) }
+        Ok (Rules (rules))
+    }
+
+}
+
+/// A rule of a grammar, like `pub sum: i64 = product ("+" product)* ;`
+struct Rule {
+    /// The attributes of the function that creates the rule's parser, like doc comments
+    attributes: Vec<Attribute>,
+    /// The expression the rule matches
+    body: Choice,
+    /// The name of the rule
+    name: Ident,
+    /// The type of the rule's output, if its output is kept
+    output: Option<Type>,
+    /// The visibility of the function that creates the rule's parser
+    visibility: Visibility,
+}
+
+impl Parse for Rule {
+
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attributes: Vec<Attribute> = input.call(Attribute::parse_outer)?;
+        let visibility: Visibility = input.parse()?;
+        let name: Ident = input.parse()?;
+        let output: Option<Type> = if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            Some (input.parse()?)
+        } else {
+            None
+        };
+        input.parse::<Token![=]>()?;
+        let body: Choice = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok (Rule { attributes, body, name, output, visibility })
+    }
+
+}
+
+/// Alternatives that are tried in order, separated by `/`
+struct Choice (Vec<Sequence>);
+
+impl Parse for Choice {
+
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut alternatives: Vec<Sequence> = vec![input.parse()?];
+        while input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            alternatives.push(input.parse()?);
+        }
+        Ok (Choice (alternatives))
+    }
+
+}
+
+/// Elements that are matched in order, with an optional action that computes their output
+struct Sequence {
+    /// The block that computes the output from the named captures
+    action: Option<Block>,
+    /// The elements
+    elements: Vec<Element>,
+}
+
+impl Parse for Sequence {
+
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut elements: Vec<Element> = Vec::new();
+        while !(input.is_empty()
+            || input.peek(Token![/])
+            || input.peek(Token![;])
+            || input.peek(token::Brace))
+        {
+            elements.push(input.parse()?);
+        }
+        if elements.is_empty() { return Err (input.error("expected an expression")) }
+        let action: Option<Block> = if input.peek(token::Brace) {
+            Some (input.parse()?)
+        } else {
+            None
+        };
+        Ok (Sequence { action, elements })
+    }
+
+}
+
+/// An expression in a sequence, which may be captured with a name like `left:product`
+struct Element {
+    /// The name the output of the expression is captured with
+    capture: Option<Ident>,
+    /// The expression
+    expression: Expression,
+}
+
+impl Parse for Element {
+
+    fn parse(input: ParseStream) -> Result<Self> {
+        let capture: Option<Ident> = if input.peek(syn::Ident) && input.peek2(Token![:]) {
+            let capture: Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+            Some (capture)
+        } else {
+            None
+        };
+        Ok (Element { capture, expression: Expression::parse_prefixed(input)? })
+    }
+
+}
+
+/// An expression of a grammar
+enum Expression {
+    /// `&expression`, which must match without consuming input
+    And (Box<Expression>),
+    /// `(alternatives)`
+    Group (Choice),
+    /// `expression @ "label"`, which is labelled as a rule of the grammar
+    Labelled (Box<Expression>, LitStr),
+    /// `!expression`, which must not match
+    Not (Box<Expression>),
+    /// `expression?`
+    Optional (Box<Expression>),
+    /// A rule or a built-in parser
+    Reference (Ident),
+    /// `expression*`, or `expression+` with a minimum of one repetition
+    Repeated (Box<Expression>, usize),
+    /// A literal token
+    Token (LitStr),
+    /// `_`, which is optional whitespace
+    Whitespace (Span),
+}
+
+impl Expression {
+
+    /// Parses an expression that may be preceded by `&` or `!`
+    fn parse_prefixed(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![&]) {
+            input.parse::<Token![&]>()?;
+            Ok (Expression::And (Box::new(Self::parse_suffixed(input)?)))
+        } else if input.peek(Token![!]) {
+            input.parse::<Token![!]>()?;
+            Ok (Expression::Not (Box::new(Self::parse_suffixed(input)?)))
+        } else {
+            Self::parse_suffixed(input)
+        }
+    }
+
+    /// Parses an expression that may be followed by `*`, `+`, `?`, or a label
+    fn parse_suffixed(input: ParseStream) -> Result<Self> {
+        let mut expression: Expression = Self::parse_primary(input)?;
+        loop {
+            expression = if input.peek(Token![*]) {
+                input.parse::<Token![*]>()?;
+                Expression::Repeated (Box::new(expression), 0)
+            } else if input.peek(Token![+]) {
+                input.parse::<Token![+]>()?;
+                Expression::Repeated (Box::new(expression), 1)
+            } else if input.peek(Token![?]) {
+                input.parse::<Token![?]>()?;
+                Expression::Optional (Box::new(expression))
+            } else if input.peek(Token![@]) {
+                input.parse::<Token![@]>()?;
+                Expression::Labelled (Box::new(expression), input.parse()?)
+            } else {
+                return Ok (expression)
+            }
+        }
+    }
+
+    /// Parses a token, a reference, optional whitespace, or a parenthesized expression
+    fn parse_primary(input: ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            Ok (Expression::Token (input.parse()?))
+        } else if input.peek(Token![_]) {
+            Ok (Expression::Whitespace (input.parse::<Token![_]>()?.span))
+        } else if input.peek(syn::Ident) {
+            Ok (Expression::Reference (input.parse()?))
+        } else if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Ok (Expression::Group (content.parse()?))
+        } else {
+            Err (input.error("expected a token, a rule, `_`, or a parenthesized expression"))
+        }
+    }
+
+}
+
+
+/// Expands the rules of a grammar into a function for each rule that creates its parser
+pub fn expand(Rules (rules): Rules) -> Result<Tokens> {
+    let names: Vec<&Ident> = rules.iter().map(|rule| &rule.name).collect();
+    let mut errors: Vec<syn::Error> = Vec::new();
+    let mut expanded: Tokens = Tokens::new();
+    for (index, rule) in rules.iter().enumerate() {
+        if names[..index].contains(&&rule.name) {
+            errors.push(syn::Error::new_spanned(&rule.name, "this rule is already defined"));
+        }
+        expanded.extend(expand_rule(rule, &names, &mut errors));
+    }
+    match errors.into_iter().reduce(|mut combined, error| {
+        combined.combine(error);
+        combined
+    }) {
+        Some (error) => Err (error),
+        None => Ok (expanded),
+    }
+}
+
+/// Expands a rule into a parser type and a function that creates it
+fn expand_rule(rule: &Rule, names: &[&Ident], errors: &mut Vec<syn::Error>) -> Tokens {
+    let Rule { attributes, body, name, output, visibility } = rule;
+    let parser: Ident = parser_type(name);
+    let label: String = name.to_string();
+    let body: Tokens = expand_choice(body, names, errors);
+    // rules without an output type only recognise input
+    let (output, body): (Tokens, Tokens) = match output {
+        Some (output) => (quote!(#output), body),
+        None => (quote!(()), quote!(::pups::mapped(#body, |_| ()))),
+    };
+    quote! {
+        #[doc(hidden)]
+        #[derive(Clone, Copy)]
+        struct #parser;
+
+        impl #parser {
+            fn body<'a>() -> impl ::pups::Parser<'a, #output, (), (), ::pups::Text> { #body }
+        }
+
+        impl<'a> ::pups::Parser<'a, #output, (), (), ::pups::Text> for #parser {
+            fn apply<_Mode: ::pups::Mode>(
+                &self,
+                input: &'a ::pups::Text,
+            ) -> ::pups::ModeResult<#output, (), (), _Mode> {
+                let tag: ::pups::SyntaxTag = ::pups::SyntaxTag::Node (#label);
+                let annotation: ::pups::Annotation = ::pups::Annotation::Syntax (tag);
+                <_Mode as ::pups::Mode>::apply_annotated(annotation, &Self::body(), input)
+            }
+
+            fn check(&self, input: &'a ::pups::Text) -> bool {
+                self.apply::<::pups::Check>(input).is_success()
+            }
+
+            fn parse(&self, input: &'a ::pups::Text) -> ::std::result::Result<#output, ()> {
+                self.apply::<::pups::Parse>(input).to_result()
+            }
+
+            fn verbose(
+                &self,
+                input: &'a ::pups::Text,
+            ) -> (::std::result::Result<#output, ()>, ::std::vec::Vec<()>) {
+                match self.apply::<::pups::Verbose>(input) {
+                    ::pups::ModeResult::Success (output, messages) => (Ok (output), messages),
+                    ::pups::ModeResult::Failure (error, messages) => (Err (error), messages),
+                }
+            }
+
+            fn apply_trace(
+                &self,
+                input: &'a ::pups::Text,
+            ) -> (::std::result::Result<#output, ()>, ::std::vec::Vec<()>) {
+                match self.apply::<::pups::Trace>(input) {
+                    ::pups::ModeResult::Success (output, messages) => (Ok (output), messages),
+                    ::pups::ModeResult::Failure (error, messages) => (Err (error), messages),
+                }
+            }
+
+            fn apply_cst(
+                &self,
+                input: &'a ::pups::Text,
+            ) -> (::std::result::Result<#output, ()>, ::std::vec::Vec<()>) {
+                match self.apply::<::pups::Cst>(input) {
+                    ::pups::ModeResult::Success (output, messages) => (Ok (output), messages),
+                    ::pups::ModeResult::Failure (error, messages) => (Err (error), messages),
+                }
+            }
+
+            fn apply_highlight(&self, input: &'a ::pups::Text) -> bool {
+                self.apply::<::pups::Highlight>(input).is_success()
+            }
+
+            fn apply_completion(&self, input: &'a ::pups::Text) -> bool {
+                self.apply::<::pups::Completion>(input).is_success()
+            }
+
+            fn describe(&self) -> ::pups::Grammar {
+                ::pups::Grammar::rule(#label, || ::pups::Parser::describe(&Self::body()))
+            }
+        }
+
+        #( #attributes )*
+        #visibility fn #name<'a>() -> impl ::pups::Parser<'a, #output, (), (), ::pups::Text> {
+            #parser
+        }
+    }
+}
+
+/// The name of the parser type of a rule
+fn parser_type(name: &Ident) -> Ident {
+    let mut camel_case: String = String::new();
+    for word in name.to_string().split('_').filter(|word| !word.is_empty()) {
+        let mut characters = word.chars();
+        camel_case.extend(characters.next().map(|first| first.to_ascii_uppercase()));
+        camel_case.extend(characters);
+    }
+    format_ident!("{}Rule", camel_case, span = name.span())
+}
+
+/// Expands alternatives into a choice between them
+fn expand_choice(Choice (alternatives): &Choice, names: &[&Ident], errors: &mut Vec<syn::Error>)
+    -> Tokens
+{
+    let alternatives: Vec<Tokens> = alternatives.iter()
+        .map(|alternative| expand_sequence(alternative, names, errors))
+        .collect();
+    // alternatives are chosen between two at a time, so there can be any number of them
+    alternatives.into_iter().rev()
+        .reduce(|rest, alternative| quote!(::pups::choice((#alternative, #rest))))
+        .expect("choices have at least one alternative")
+}
+
+/// Expands a sequence into parsers that are applied in order, with an output that is computed by
+/// its action, or that is its only element, or that is a tuple of its named captures otherwise
+fn expand_sequence(sequence: &Sequence, names: &[&Ident], errors: &mut Vec<syn::Error>) -> Tokens {
+    let Sequence { action, elements } = sequence;
+    let mut parsers: Vec<Tokens> = elements.iter()
+        .map(|element| expand_expression(&element.expression, names, errors))
+        .collect();
+    if parsers.len() == 1 && action.is_none() { return parsers.remove(0) }
+    let captures: Vec<&Ident> = elements.iter()
+        .filter_map(|element| element.capture.as_ref())
+        .collect();
+    // elements are sequenced two at a time, so there can be any number of them
+    let mut patterns = elements.iter().map(|element| match &element.capture {
+        Some (capture) => quote!(#capture),
+        None => quote!(_),
+    }).rev();
+    let mut pattern: Tokens = patterns.next().expect("sequences have at least one element");
+    for element in patterns { pattern = quote!((#element, #pattern)) }
+    let parser: Tokens = parsers.into_iter().rev()
+        .reduce(|rest, parser| quote!(::pups::sequenced(#parser, #rest)))
+        .expect("sequences have at least one element");
+    let output: Tokens = match (action, captures.as_slice()) {
+        (Some (action), _) => quote!(#action),
+        (None, [capture]) => quote!(#capture),
+        (None, captures) => quote!(( #( #captures ),* )),
+    };
+    quote!(::pups::mapped(#parser, |#pattern| #output))
+}
+
+/// Expands an expression into a parser
+fn expand_expression(
+    expression: &Expression,
+    names: &[&Ident],
+    errors: &mut Vec<syn::Error>,
+) -> Tokens {
+    let nested = match expression {
+        Expression::Group (choice) => return expand_choice(choice, names, errors),
+        Expression::Reference (name) => return expand_reference(name, names, errors),
+        Expression::Token (lexeme) => return quote!(::pups::token::<::pups::Text>(#lexeme)),
+        Expression::Whitespace (span) => {
+            let whitespace: Tokens = quote!(::pups::whitespace::<_, ::pups::Text>());
+            return quote_spanned!(*span=> ::pups::optional(#whitespace))
+        }
+        Expression::And (nested)
+        | Expression::Labelled (nested, _)
+        | Expression::Not (nested)
+        | Expression::Optional (nested)
+        | Expression::Repeated (nested, _) => expand_expression(nested, names, errors),
+    };
+    match expression {
+        Expression::And (_) => quote!(::pups::mapped(::pups::lookahead(#nested), |_| ())),
+        Expression::Labelled (_, label) => quote!(::pups::labelled(#nested, #label)),
+        Expression::Not (_) => quote!(::pups::not(#nested, |_| ())),
+        Expression::Optional (_) => quote!(::pups::optional(#nested)),
+        Expression::Repeated (_, 0) => quote!(::pups::repeated(#nested)),
+        Expression::Repeated (_, minimum) => quote!(::pups::repeated_at_least(#nested, #minimum)),
+        _ => unreachable!("expressions without a nested expression are expanded already"),
+    }
+}
+
+/// Expands a reference to a rule of the grammar, or to a built-in parser if there is no rule with
+/// its name
+fn expand_reference(name: &Ident, names: &[&Ident], errors: &mut Vec<syn::Error>) -> Tokens {
+    if names.contains(&name) {
+        let parser: Ident = parser_type(name);
+        return quote!(#parser)
+    }
+    // the built-in parsers are given their input type so the outputs of sequences are known
+    // before the types of their actions are checked
+    let built_in: Tokens = match name.to_string().as_str() {
+        "end" => quote!(end::<::pups::Text>),
+        "identifier" => quote!(unicode_identifier::<_, ::pups::Text>),
+        "newline" => quote!(newline::<_, ::pups::Text>),
+        "number" => quote!(number::<_, ::pups::Text>),
+        "whitespace" => quote!(whitespace::<_, ::pups::Text>),
+        _ => {
+            errors.push(syn::Error::new_spanned(name, format!("undefined rule `{name}`")));
+            return quote!(::pups::nothing())
+        }
+    };
+    quote_spanned!(name.span()=> ::pups::#built_in())
+}
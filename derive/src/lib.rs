@@ -0,0 +1,295 @@
+// Copyright Rob Gage 2025
+
+mod attributes;
+mod grammar;
+
+use attributes::{
+    FieldAttributes,
+    Leaf,
+    TypeAttributes,
+};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as Tokens;
+use quote::{
+    format_ident,
+    quote,
+};
+use syn::{
+    Data,
+    DeriveInput,
+    Fields,
+    GenericArgument,
+    Ident,
+    LitStr,
+    parse_macro_input,
+    PathArguments,
+    Result,
+    Type,
+};
+
+
+/// Derives `Parsable` for a struct or an enum, so it can be parsed from `Text`
+///
+/// The fields of a struct are parsed in sequence, and the variants of an enum are tried in order,
+/// with the fields of each variant parsed in sequence. Field values are parsed with the
+/// `Parsable` implementation of their type, `Option` fields are optional along with their tokens,
+/// and `Vec` fields are repeated. Optional whitespace is skipped before every token and field.
+///
+/// Structs, enums, and variants accept these `#[pups(...)]` attributes:
+/// - `prefix = "..."` and `suffix = "..."` for tokens before and after the fields
+/// - `no_whitespace` to leave whitespace to the parsers, which variants inherit from their enum
+///
+/// Fields accept these `#[pups(...)]` attributes:
+/// - `prefix = "..."` and `suffix = "..."` for tokens before and after the field
+/// - `identifier` to parse the value with `unicode_identifier`, converting it with `From<&str>`
+/// - `number` to parse the value with `number`, converting it with `FromStr`
+/// - `parser = ...` to parse the value with a parser expression
+/// - `separator = "..."`, `at_least = ...`, and `trailing` for the items of `Vec` fields
+///
+/// The value attributes apply to the items of `Option` and `Vec` fields.
+#[proc_macro_derive(Parse, attributes(pups))]
+pub fn derive_parse(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    match implement_parsable(&input) {
+        Ok (implementation) => implementation.into(),
+        Err (error) => error.to_compile_error().into(),
+    }
+}
+
+
+/// Defines parsers for the rules of a PEG-style grammar, with a function for each rule that
+/// creates its parser
+///
+/// Each rule is written as `name: Type = expression;`, and rules can refer to each other and to
+/// themselves, as long as they are not left recursive. Rules without a type only recognise input,
+/// and every rule is labelled with its name, so it is a rule of the described grammar and a node
+/// of concrete syntax trees. Expressions are written with:
+/// - `"..."` for a token
+/// - `rule` for another rule, or for one of the built-in parsers `end`, `identifier`, `newline`,
+///   `number`, and `whitespace` if there is no rule with that name
+/// - `_` for optional whitespace
+/// - `a b` for a sequence, and `a / b` for alternatives that are tried in order
+/// - `a*`, `a+`, and `a?` for repetitions and optional input
+/// - `&a` and `!a` for input that must or must not follow, without consuming it
+/// - `a @ "label"` to label an expression as a rule of the described grammar
+/// - `name:a` to capture the output of an expression in a sequence
+/// - `{ ... }` after a sequence for an action that computes its output from its captures
+///
+/// A sequence without an action outputs its only element, or a tuple of its captures otherwise.
+///
+/// ```ignore
+/// grammar! {
+///     pub sum: i64 = first:product rest:(_ operator:("+" / "-") _ next:product)* {
+///         rest.into_iter().fold(first, |sum, (operator, next)| match operator {
+///             "+" => sum + next,
+///             _ => sum - next,
+///         })
+///     };
+///     product: i64 = number:number { number.parse().unwrap() } / "(" _ sum:sum _ ")" { sum };
+/// }
+/// ```
+#[proc_macro]
+pub fn grammar(input: TokenStream) -> TokenStream {
+    let rules: grammar::Rules = parse_macro_input!(input as grammar::Rules);
+    match grammar::expand(rules) {
+        Ok (expanded) => expanded.into(),
+        Err (error) => error.to_compile_error().into(),
+    }
+}
+
+
+/// A parser in a sequence, with the pattern its output is bound to and the type of its output
+struct Element {
+    /// The pattern the output is bound to
+    binding: Tokens,
+    /// The type of the output
+    output: Tokens,
+    /// The parser
+    parser: Tokens,
+}
+
+/// Implements `Parsable` for a struct or an enum
+fn implement_parsable(input: &DeriveInput) -> Result<Tokens> {
+    let name: &Ident = &input.ident;
+    let attributes: TypeAttributes = TypeAttributes::read(&input.attrs)?;
+    let parser: Tokens = match &input.data {
+        Data::Struct (data) => constructor(quote!(#name), &data.fields, &attributes)?,
+        Data::Enum (data) => {
+            let mut variants: Vec<Tokens> = Vec::new();
+            for variant in &data.variants {
+                let identifier: &Ident = &variant.ident;
+                let mut variant_attributes: TypeAttributes = TypeAttributes::read(&variant.attrs)?;
+                variant_attributes.no_whitespace |= attributes.no_whitespace;
+                let path: Tokens = quote!(#name::#identifier);
+                variants.push(constructor(path, &variant.fields, &variant_attributes)?);
+            }
+            // variants are chosen between two at a time, so there can be any number of them
+            let choice = variants.into_iter().rev()
+                .reduce(|rest, variant| quote!(::pups::choice((#variant, #rest))));
+            let message: &str = "cannot parse an enum without variants";
+            choice.ok_or_else(|| syn::Error::new_spanned(name, message))?
+        }
+        Data::Union (_) => return Err (syn::Error::new_spanned(name, "cannot parse a union")),
+    };
+    let (implementation_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok (quote! {
+        impl #implementation_generics ::pups::Parsable for #name #type_generics #where_clause {
+            fn apply<_Mode: ::pups::Mode>(
+                input: &::pups::Text
+            ) -> ::pups::ModeResult<Self, (), (), _Mode> {
+                let parser = #parser;
+                <_Mode as ::pups::Mode>::apply_nested(&parser, input)
+            }
+        }
+    })
+}
+
+/// Creates a parser that parses the fields of a struct or a variant in sequence and constructs it
+fn constructor(
+    path: Tokens,
+    fields: &Fields,
+    attributes: &TypeAttributes,
+) -> Result<Tokens> {
+    let whitespace: bool = !attributes.no_whitespace;
+    let mut elements: Vec<Element> = tokens(&attributes.prefixes, whitespace);
+    let mut bindings: Vec<Ident> = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let field_attributes: FieldAttributes = FieldAttributes::read(&field.attrs)?;
+        let binding: Ident = field.ident.clone().unwrap_or_else(|| format_ident!("field_{index}"));
+        elements.extend(field_elements(&binding, &field.ty, &field_attributes, whitespace)?);
+        bindings.push(binding);
+    }
+    elements.extend(tokens(&attributes.suffixes, whitespace));
+    let value: Tokens = match fields {
+        Fields::Named (_) => quote!(#path { #( #bindings ),* }),
+        Fields::Unnamed (_) => quote!(#path ( #( #bindings ),* )),
+        Fields::Unit => quote!(#path),
+    };
+    // elements are sequenced two at a time, so there can be any number of them
+    let Some (last) = elements.pop() else {
+        return Ok (quote!(::pups::mapped(::pups::nothing(), |(): ()| #value)))
+    };
+    let sequenced: Element = elements.into_iter().rev().fold(last, |rest, element| {
+        let (binding, rest_binding) = (element.binding, rest.binding);
+        let (output, rest_output) = (element.output, rest.output);
+        let (parser, rest_parser) = (element.parser, rest.parser);
+        Element {
+            binding: quote!((#binding, #rest_binding)),
+            output: quote!((#output, #rest_output)),
+            parser: quote!(::pups::sequenced(#parser, #rest_parser)),
+        }
+    });
+    let Element { binding, output, parser } = sequenced;
+    Ok (quote!(::pups::mapped(#parser, |#binding: #output| #value)))
+}
+
+/// Creates the elements that parse a field and its tokens
+fn field_elements(
+    binding: &Ident,
+    field_type: &Type,
+    attributes: &FieldAttributes,
+    whitespace: bool,
+) -> Result<Vec<Element>> {
+    let mut prefixes: Vec<Element> = tokens(&attributes.prefixes, whitespace);
+    let mut suffixes: Vec<Element> = tokens(&attributes.suffixes, whitespace);
+    let parser: Tokens = match wrapped(field_type) {
+        // the tokens of an optional field are optional along with its value
+        Some (("Option", item)) => {
+            let mut item: Tokens = skipping_whitespace(leaf(item, &attributes.leaf), whitespace);
+            for prefix in prefixes.drain(..).rev().map(|prefix| prefix.parser) {
+                item = quote!(::pups::preceded(#prefix, #item));
+            }
+            for suffix in suffixes.drain(..).map(|suffix| suffix.parser) {
+                item = quote!(::pups::terminated(#item, #suffix));
+            }
+            quote!(::pups::optional(#item))
+        }
+        Some (("Vec", item)) => {
+            let item: Tokens = skipping_whitespace(leaf(item, &attributes.leaf), whitespace);
+            let mut parser: Tokens = match &attributes.separator {
+                Some (separator) => {
+                    let separator: Tokens = token(separator, whitespace).parser;
+                    quote!(::pups::separated(#item, #separator))
+                }
+                None => quote!(::pups::repeated(#item)),
+            };
+            if let Some (minimum) = &attributes.minimum {
+                parser = quote!(#parser.at_least(#minimum));
+            }
+            if attributes.trailing { parser = quote!(#parser.allow_trailing()) }
+            parser
+        }
+        _ => {
+            let repetition: bool = attributes.minimum.is_some()
+                || attributes.separator.is_some()
+                || attributes.trailing;
+            if repetition {
+                let message: &str = "only `Vec` fields are repeated";
+                return Err (syn::Error::new_spanned(field_type, message))
+            }
+            skipping_whitespace(leaf(field_type, &attributes.leaf), whitespace)
+        }
+    };
+    let value: Element = Element { binding: quote!(#binding), output: quote!(#field_type), parser };
+    Ok (prefixes.into_iter().chain([value]).chain(suffixes).collect())
+}
+
+/// Creates the parser for the value of a field, or of each of its items
+fn leaf(value_type: &Type, leaf: &Leaf) -> Tokens {
+    match leaf {
+        Leaf::Identifier => quote! {
+            ::pups::mapped(
+                ::pups::unicode_identifier(),
+                |identifier: &str| <#value_type as ::std::convert::From<&str>>::from(identifier),
+            )
+        },
+        Leaf::Number => quote! {
+            ::pups::try_map(
+                ::pups::number(),
+                |number: &str, _| number.parse::<#value_type>().map_err(|_| ()),
+            )
+        },
+        Leaf::Parsable => quote!(::pups::parsed::<#value_type>()),
+        Leaf::Parser (parser) => quote!(#parser),
+    }
+}
+
+/// Creates the element that parses a token
+fn token(lexeme: &LitStr, whitespace: bool) -> Element {
+    let parser: Tokens = quote!(::pups::emitting(::pups::token(#lexeme), ()));
+    Element {
+        binding: quote!(_),
+        output: quote!(()),
+        parser: skipping_whitespace(parser, whitespace),
+    }
+}
+
+/// Creates the elements that parse tokens in sequence
+fn tokens(lexemes: &[LitStr], whitespace: bool) -> Vec<Element> {
+    lexemes.iter().map(|lexeme| token(lexeme, whitespace)).collect()
+}
+
+/// Wraps a parser so optional whitespace is skipped before it
+fn skipping_whitespace(parser: Tokens, whitespace: bool) -> Tokens {
+    if whitespace {
+        quote!(::pups::preceded(::pups::optional(::pups::whitespace()), #parser))
+    } else {
+        parser
+    }
+}
+
+/// Returns the name of a wrapper type like `Option` or `Vec` and the type it wraps
+fn wrapped(field_type: &Type) -> Option<(&'static str, &Type)> {
+    let Type::Path (path) = field_type else { return None };
+    let segment = path.path.segments.last()?;
+    let wrapper: &'static str = match segment.ident.to_string().as_str() {
+        "Option" => "Option",
+        "Vec" => "Vec",
+        _ => return None,
+    };
+    let PathArguments::AngleBracketed (arguments) = &segment.arguments else { return None };
+    match arguments.args.first()? {
+        GenericArgument::Type (item) if arguments.args.len() == 1 => Some ((wrapper, item)),
+        _ => None,
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright Rob Gage 2025
+
+use pups::*;
+
+
+/// A statement that binds an expression to a name
+#[derive(Debug, Parse, PartialEq)]
+#[pups(prefix = "let", suffix = ";")]
+struct Binding {
+    #[pups(identifier)]
+    name: String,
+    #[pups(prefix = "=")]
+    value: Expression,
+}
+
+/// A call of a named function
+#[derive(Debug, Parse, PartialEq)]
+struct Call {
+    #[pups(identifier)]
+    function: String,
+    #[pups(prefix = "(", separator = ",", suffix = ")")]
+    arguments: Vec<Expression>,
+}
+
+/// An expression
+#[derive(Debug, Parse, PartialEq)]
+enum Expression {
+    Call (Call),
+    #[pups(prefix = "[", suffix = "]")]
+    List (#[pups(separator = ",", trailing)] Vec<Expression>),
+    Negated (#[pups(prefix = "-")] Box<Expression>),
+    Number (#[pups(number)] u64),
+    Variable (#[pups(identifier)] String),
+}
+
+/// A pair of numbers separated by a colon without any whitespace
+#[derive(Debug, Parse, PartialEq)]
+#[pups(no_whitespace)]
+struct Pair {
+    #[pups(number)]
+    left: u32,
+    #[pups(prefix = ":", number)]
+    right: u32,
+}
+
+/// A block of statements that may be empty
+#[derive(Debug, Parse, PartialEq)]
+struct Block {
+    #[pups(prefix = "{", suffix = "}")]
+    statements: Vec<Binding>,
+    #[pups(prefix = "->", identifier)]
+    result: Option<String>,
+}
+
+/// Parses the whole of some text
+fn parse_all<T: Parsable>(text: &str) -> Result<T, ()> {
+    let input: Text = Text::from_string(text);
+    terminated(parsed::<T>(), end()).parse(&input)
+}
+
+
+#[test]
+fn fields_are_parsed_in_sequence_with_their_tokens() {
+    assert_eq!(
+        parse_all::<Binding>("let total = sum(1, -x, [2, 3,]);"),
+        Ok (Binding {
+            name: "total".to_string(),
+            value: Expression::Call (Call {
+                function: "sum".to_string(),
+                arguments: vec![
+                    Expression::Number (1),
+                    Expression::Negated (Box::new(Expression::Variable ("x".to_string()))),
+                    Expression::List (vec![Expression::Number (2), Expression::Number (3)]),
+                ],
+            }),
+        })
+    );
+    assert_eq!(parse_all::<Binding>("let total = ;"), Err (()));
+}
+
+#[test]
+fn variants_are_tried_in_order() {
+    assert_eq!(parse_all::<Expression>("f()"), Ok (Expression::Call (Call {
+        function: "f".to_string(),
+        arguments: Vec::new(),
+    })));
+    assert_eq!(parse_all::<Expression>("f"), Ok (Expression::Variable ("f".to_string())));
+    assert_eq!(parse_all::<Expression>("--4"), Ok (Expression::Negated (Box::new(
+        Expression::Negated (Box::new(Expression::Number (4)))
+    ))));
+}
+
+#[test]
+fn optional_fields_and_empty_repetitions_are_parsed() {
+    assert_eq!(parse_all::<Block>("{ }"), Ok (Block { statements: Vec::new(), result: None }));
+    assert_eq!(
+        parse_all::<Block>("{ let x = 1; let y = x; } -> y"),
+        Ok (Block {
+            statements: vec![
+                Binding { name: "x".to_string(), value: Expression::Number (1) },
+                Binding {
+                    name: "y".to_string(),
+                    value: Expression::Variable ("x".to_string()),
+                },
+            ],
+            result: Some ("y".to_string()),
+        })
+    );
+}
+
+#[test]
+fn whitespace_is_only_skipped_where_it_is_allowed() {
+    assert_eq!(parse_all::<Pair>("1:2"), Ok (Pair { left: 1, right: 2 }));
+    assert_eq!(parse_all::<Pair>("1 : 2"), Err (()));
+}
+
+#[test]
+fn derived_parsers_work_in_every_mode() {
+    let text: &str = "let x = [1, y];";
+    assert!(Binding::parser().check(&Text::from_string(text)));
+    let input: Text = Text::from_string(text);
+    let (result, messages): (Result<Binding, ()>, Vec<()>) = Binding::parser().verbose(&input);
+    assert!(result.is_ok());
+    assert!(messages.is_empty());
+    let input: Text = Text::from_string(text);
+    let trivia = || whitespace().or_not();
+    let bindings = repeated(terminated(Binding::parser(), trivia()));
+    assert_eq!(bindings.parse(&input).map(|bindings| bindings.len()), Ok (1));
+}
@@ -0,0 +1,85 @@
+// Copyright Rob Gage 2025
+
+use pups::*;
+
+
+grammar! {
+    /// A sum of products
+    pub sum: i64 = first:product rest:(_ operator:("+" / "-") _ next:product)* {
+        rest.into_iter().fold(first, |sum, (operator, next)| match operator {
+            "+" => sum + next,
+            _ => sum - next,
+        })
+    };
+    product: i64 = first:atom rest:(_ "*" _ next:atom)* {
+        rest.into_iter().fold(first, |product, next| product * next)
+    };
+    atom: i64 = digits:number @ "integer" { digits.parse().unwrap() }
+        / "(" _ inner:sum _ ")" { inner }
+        / "-" _ negated:atom { -negated };
+
+    /// A list of names, which is only recognised
+    names = "[" _ (name (_ "," _ name)*)? _ "]";
+    name = !keyword identifier;
+    keyword = ("let" / "in") !identifier;
+
+    /// A binding of a name to a value
+    binding: (&'a str, i64) = "let" whitespace name:identifier _ "=" _ value:sum;
+}
+
+/// Parses the whole of some text with a parser
+fn parse_all<'a, O>(parser: impl Parser<'a, O, (), (), Text>, input: &'a Text) -> Result<O, ()> {
+    terminated(parser, end()).parse(input)
+}
+
+
+#[test]
+fn actions_compute_outputs_from_captures() {
+    let input: Text = Text::from_string("1 + 2 * (3 - -4) - 5");
+    assert_eq!(parse_all(sum(), &input), Ok (10));
+    let input: Text = Text::from_string("2 * 3 * 4");
+    assert_eq!(parse_all(sum(), &input), Ok (24));
+    let input: Text = Text::from_string("1 + ");
+    assert_eq!(parse_all(sum(), &input), Err (()));
+}
+
+#[test]
+fn sequences_without_actions_output_their_captures() {
+    let input: Text = Text::from_string("let answer = 6 * 7");
+    assert_eq!(parse_all(binding(), &input), Ok (("answer", 42)));
+}
+
+#[test]
+fn rules_without_types_recognise_input() {
+    let recognised = |text: &str| {
+        let input: Text = Text::from_string(text);
+        names().check(&input)
+    };
+    assert!(recognised("[a, b,c]"));
+    assert!(recognised("[ ]"));
+    assert!(!recognised("[a, let]"));
+    assert!(recognised("[a, letter]"));
+}
+
+#[test]
+fn recursive_rules_describe_themselves_once() {
+    assert_eq!(
+        sum().describe().to_ebnf(),
+        "sum = product , { [ ? whitespace ? ] , ( \"+\" | \"-\" ) , [ ? whitespace ? ] , \
+        product } ;\n\
+        product = atom , { [ ? whitespace ? ] , \"*\" , [ ? whitespace ? ] , atom } ;\n\
+        atom = integer | \"(\" , [ ? whitespace ? ] , sum , [ ? whitespace ? ] , \")\" | \"-\" , \
+        [ ? whitespace ? ] , atom ;\n\
+        integer = ? number ? ;\n"
+    );
+}
+
+#[test]
+fn rules_are_nodes_of_concrete_syntax_trees() {
+    let input: Text = Text::from_string("(1)");
+    let (_, tree): (ModeResult<i64, (), (), Cst>, SyntaxNode) = Cst::run(&sum(), &input);
+    let sum: SyntaxNode = tree.children().next().unwrap();
+    assert_eq!(sum.kind(), "sum");
+    let nested: Vec<&str> = sum.children().map(|node| node.kind()).collect();
+    assert_eq!(nested, ["product"]);
+}
@@ -2,4 +2,14 @@
 
 pub use pups_core::prelude::*;
 
+pub use pups_core::{
+    Annotation,
+    Mode,
+};
+
+pub use pups_derive::{
+    grammar,
+    Parse,
+};
+
 pub use pups_text::prelude::*;
\ No newline at end of file
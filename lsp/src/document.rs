@@ -0,0 +1,100 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Analysis,
+    Language,
+};
+use pups_core::Span;
+use pups_text::{
+    Text,
+    TextEdit,
+};
+use serde_json::{
+    json,
+    Value,
+};
+
+/// An open document and the analysis of its current text
+pub(crate) struct Document {
+    /// The analysis of the text
+    pub(crate) analysis: Analysis,
+    /// The byte offsets where each line of the text starts
+    line_starts: Vec<usize>,
+    /// The text of the document
+    pub(crate) text: Text,
+}
+
+impl Document {
+
+    /// Creates a new `Document` from its text, analyzing it
+    pub(crate) fn new(language: &impl Language, text: &str) -> Self {
+        let text: Text = Text::from_string(text);
+        let analysis: Analysis = language.analyze(&text);
+        Self { analysis, line_starts: line_starts(text.as_str()), text }
+    }
+
+    /// Edits the text of this document, replacing a range or all of the text if there is no range
+    pub(crate) fn edit(&mut self, range: Option<&Value>, replacement: &str) {
+        let span: Span = match range {
+            Some (range) => Span::new(
+                self.offset(&range["start"]),
+                self.offset(&range["end"]),
+            ),
+            None => Span::new(0, self.text.as_str().len()),
+        };
+        // clients may send a range that ends before it starts
+        let span: Span = Span::new(span.start.min(span.end), span.start.max(span.end));
+        self.text.apply_edit(&TextEdit::new(span, replacement));
+        self.line_starts = line_starts(self.text.as_str());
+    }
+
+    /// Analyzes the current text of this document
+    pub(crate) fn analyze(&mut self, language: &impl Language) {
+        self.analysis = language.analyze(&self.text);
+    }
+
+    /// Converts an LSP position, with a line and a character offset counted in UTF-16 code units,
+    /// to a byte offset in the text
+    pub(crate) fn offset(&self, position: &Value) -> usize {
+        let text: &str = self.text.as_str();
+        let line: usize = position["line"].as_u64().unwrap_or_default() as usize;
+        let Some (&line_start) = self.line_starts.get(line) else { return text.len() };
+        let mut remaining: usize = position["character"].as_u64().unwrap_or_default() as usize;
+        let mut offset: usize = line_start;
+        for character in text[line_start..].chars() {
+            if remaining == 0 || character == '\n' { break }
+            remaining = remaining.saturating_sub(character.len_utf16());
+            offset += character.len_utf8();
+        }
+        offset
+    }
+
+    /// Converts a byte offset in the text to an LSP position
+    pub(crate) fn position(&self, offset: usize) -> (usize, usize) {
+        let line: usize = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start: usize = self.line_starts[line];
+        let character: usize = self.text.as_str()[line_start..offset].encode_utf16().count();
+        (line, character)
+    }
+
+    /// Converts a span of byte offsets in the text to an LSP range
+    pub(crate) fn range(&self, span: Span) -> Value {
+        let (start_line, start_character): (usize, usize) = self.position(span.start);
+        let (end_line, end_character): (usize, usize) = self.position(span.end);
+        json!({
+            "start": { "line": start_line, "character": start_character },
+            "end": { "line": end_line, "character": end_character },
+        })
+    }
+
+    /// The byte offsets where each line of the text starts
+    pub(crate) fn line_starts(&self) -> &[usize] { &self.line_starts }
+
+}
+
+/// Returns the byte offsets where each line of a text starts
+fn line_starts(text: &str) -> Vec<usize> {
+    let mut starts: Vec<usize> = vec![0];
+    starts.extend(text.match_indices('\n').map(|(index, _)| index + 1));
+    starts
+}
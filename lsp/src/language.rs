@@ -0,0 +1,175 @@
+// Copyright Rob Gage 2025
+
+use pups_core::{
+    Cst,
+    Diagnostic,
+    Input,
+    ModeResult,
+    Parser,
+    Span,
+    SyntaxElement,
+    SyntaxNode,
+};
+use pups_text::Text;
+
+/// The semantic token types defined by the Language Server Protocol
+pub const STANDARD_TOKEN_TYPES: [&str; 23] = [
+    "namespace",
+    "type",
+    "class",
+    "enum",
+    "interface",
+    "struct",
+    "typeParameter",
+    "parameter",
+    "variable",
+    "property",
+    "enumMember",
+    "event",
+    "function",
+    "method",
+    "macro",
+    "keyword",
+    "modifier",
+    "comment",
+    "string",
+    "number",
+    "regexp",
+    "operator",
+    "decorator",
+];
+
+
+/// The kind of a document symbol, as defined by the Language Server Protocol
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SymbolKind {
+    /// A file
+    File = 1,
+    /// A module
+    Module = 2,
+    /// A namespace
+    Namespace = 3,
+    /// A package
+    Package = 4,
+    /// A class
+    Class = 5,
+    /// A method
+    Method = 6,
+    /// A property
+    Property = 7,
+    /// A field
+    Field = 8,
+    /// A constructor
+    Constructor = 9,
+    /// An enumeration
+    Enum = 10,
+    /// An interface
+    Interface = 11,
+    /// A function
+    Function = 12,
+    /// A variable
+    Variable = 13,
+    /// A constant
+    Constant = 14,
+    /// A string
+    String = 15,
+    /// A number
+    Number = 16,
+    /// A boolean
+    Boolean = 17,
+    /// An array
+    Array = 18,
+    /// An object
+    Object = 19,
+    /// A key
+    Key = 20,
+    /// A null value
+    Null = 21,
+    /// A member of an enumeration
+    EnumMember = 22,
+    /// A structure
+    Struct = 23,
+    /// An event
+    Event = 24,
+    /// An operator
+    Operator = 25,
+    /// A type parameter
+    TypeParameter = 26,
+}
+
+
+/// The result of parsing a document, which language features are provided from
+pub struct Analysis {
+    /// The diagnostics of the document
+    pub diagnostics: Vec<Diagnostic>,
+    /// The concrete syntax tree of the document
+    pub tree: SyntaxNode,
+}
+
+impl Analysis {
+
+    /// Analyzes a document by parsing all of it in `Cst` mode, using the parser's messages as
+    /// diagnostics and reporting input that could not be parsed as an error
+    pub fn new<'a, O, E, M, P>(parser: &P, text: &'a Text) -> Self
+    where
+        M: Into<Diagnostic>,
+        P: Parser<'a, O, E, M, Text>,
+    {
+        text.move_cursor(0);
+        let (result, tree): (ModeResult<O, E, M, Cst>, SyntaxNode) = Cst::run(parser, text);
+        let (success, messages): (bool, Vec<M>) = match result {
+            ModeResult::Success (_, messages) => (true, messages),
+            ModeResult::Failure (_, messages) => (false, messages),
+        };
+        let mut diagnostics: Vec<Diagnostic> = messages.into_iter().map(Into::into).collect();
+        let unparsed: Option<Span> = match tree.children_with_tokens().last() {
+            Some (SyntaxElement::Token (token)) if token.kind() == Cst::UNPARSED
+                => Some (token.span()),
+            _ => None,
+        };
+        match (success, unparsed) {
+            (true, None) => {}
+            (true, Some (span)) => diagnostics.push(Diagnostic::error(span, "unexpected input")),
+            (false, span) => {
+                let end: usize = tree.span().end;
+                let span: Span = span.unwrap_or(Span::new(end, end));
+                diagnostics.push(Diagnostic::error(span, "invalid syntax"));
+            }
+        }
+        diagnostics.sort();
+        Self { diagnostics, tree }
+    }
+
+}
+
+
+/// Implementors describe a language that a `Server` provides features for
+pub trait Language {
+
+    /// Analyzes the text of a document
+    fn analyze(&self, text: &Text) -> Analysis;
+
+    /// The name of the language server, which is also the source of its diagnostics
+    fn name(&self) -> &str { "pups" }
+
+    /// The name of the document symbol for a node
+    fn symbol_name(&self, node: &SyntaxNode) -> String {
+        let text: String = node.text();
+        text.trim().lines().next().unwrap_or_default().to_string()
+    }
+
+    /// The kind of the document symbol for a node, or `None` if it is not a document symbol
+    fn symbol_kind(&self, _node: &SyntaxNode) -> Option<SymbolKind> { Some (SymbolKind::Object) }
+
+    /// The semantic token types of the language, where tokens with the kind of a type are
+    /// semantic tokens of that type
+    fn token_types(&self) -> &[&'static str] { &STANDARD_TOKEN_TYPES }
+
+}
+
+impl<F> Language for F
+where
+    F: Fn(&Text) -> Analysis,
+{
+    fn analyze(&self, text: &Text) -> Analysis { self(text) }
+}
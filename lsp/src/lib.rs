@@ -0,0 +1,20 @@
+// Copyright Rob Gage 2025
+
+mod document;
+mod language;
+mod server;
+mod transport;
+
+use document::Document;
+
+pub use language::{
+    Analysis,
+    Language,
+    STANDARD_TOKEN_TYPES,
+    SymbolKind,
+};
+pub use server::Server;
+pub use transport::{
+    read_message,
+    write_message,
+};
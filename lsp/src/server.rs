@@ -0,0 +1,278 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    Document,
+    Language,
+    read_message,
+    write_message,
+};
+use pups_core::{
+    Diagnostic,
+    Severity,
+    Span,
+    SyntaxNode,
+    SyntaxToken,
+};
+use serde_json::{
+    json,
+    Value,
+};
+use std::{
+    collections::HashMap,
+    io::{
+        BufRead,
+        ErrorKind,
+        Result,
+        stdin,
+        stdout,
+        Write,
+    },
+};
+
+/// The error code for a message that is not valid JSON
+const PARSE_ERROR: i64 = -32700;
+
+/// The error code for a request that is not a valid request object
+const INVALID_REQUEST: i64 = -32600;
+
+/// The error code for a request with a method that the server does not provide
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The error code for a request about a document that is not open
+const INVALID_PARAMS: i64 = -32602;
+
+
+/// A language server that provides diagnostics, document symbols, and semantic tokens for a
+/// `Language` over the Language Server Protocol
+///
+/// Document symbols are made from the labelled nodes of the concrete syntax tree of a document, and
+/// semantic tokens from tokens whose kind is one of the language's token types.
+pub struct Server<L> {
+    /// The documents that are open, keyed by URI
+    documents: HashMap<String, Document>,
+    /// The language that the server provides features for
+    language: L,
+}
+
+impl<L> Server<L>
+where
+    L: Language,
+{
+
+    /// Creates a new `Server` for a `Language`
+    pub fn new(language: L) -> Self { Self { documents: HashMap::new(), language } }
+
+    /// Serves a client over standard input and output until it exits
+    pub fn run(self) -> Result<()> { self.serve(stdin().lock(), stdout().lock()) }
+
+    /// Serves a client that sends messages to a reader and receives messages from a writer, until
+    /// it exits, the reader ends, or a message cannot be framed
+    pub fn serve(mut self, mut reader: impl BufRead, mut writer: impl Write) -> Result<()> {
+        loop {
+            let message: Value = match read_message(&mut reader) {
+                Ok (Some (message)) => message,
+                Ok (None) => return Ok (()),
+                Err (error) if error.kind() == ErrorKind::InvalidData => {
+                    let response: Value = error_response(Value::Null, PARSE_ERROR, error);
+                    write_message(&mut writer, &response)?;
+                    continue
+                }
+                Err (error) => return Err (error),
+            };
+            let method: &str = message["method"].as_str().unwrap_or_default();
+            if method == "exit" { return Ok (()) }
+            let Some (id) = message.get("id").cloned() else {
+                self.notify(method, &message["params"], &mut writer)?;
+                continue
+            };
+            let response: Value = match self.respond(method, &message["params"]) {
+                Ok (result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Err ((code, error)) => error_response(id, code, error),
+            };
+            write_message(&mut writer, &response)?;
+        }
+    }
+
+    /// Handles a notification from the client
+    fn notify(&mut self, method: &str, parameters: &Value, writer: &mut impl Write) -> Result<()> {
+        let uri: &str = parameters["textDocument"]["uri"].as_str().unwrap_or_default();
+        match method {
+            "textDocument/didOpen" => {
+                let text: &str = parameters["textDocument"]["text"].as_str().unwrap_or_default();
+                let document: Document = Document::new(&self.language, text);
+                self.documents.insert(uri.to_string(), document);
+            }
+            "textDocument/didChange" => {
+                let Some (document) = self.documents.get_mut(uri) else { return Ok (()) };
+                for change in parameters["contentChanges"].as_array().into_iter().flatten() {
+                    let text: &str = change["text"].as_str().unwrap_or_default();
+                    document.edit(change.get("range"), text);
+                }
+                document.analyze(&self.language);
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                let parameters: Value = json!({ "uri": uri, "diagnostics": [] });
+                return publish_diagnostics(writer, parameters)
+            }
+            _ => return Ok (()),
+        }
+        let document: &Document = &self.documents[uri];
+        let diagnostics: Vec<Value> = document.analysis.diagnostics.iter()
+            .map(|diagnostic| self.diagnostic(document, uri, diagnostic))
+            .collect();
+        publish_diagnostics(writer, json!({ "uri": uri, "diagnostics": diagnostics }))
+    }
+
+    /// Handles a request from the client, returning its result or an error code and message
+    fn respond(
+        &self,
+        method: &str,
+        parameters: &Value,
+    ) -> std::result::Result<Value, (i64, String)> {
+        if method.is_empty() { return Err ((INVALID_REQUEST, "request has no method".into())) }
+        let document = || {
+            let uri: &str = parameters["textDocument"]["uri"].as_str().unwrap_or_default();
+            self.documents.get(uri).ok_or((INVALID_PARAMS, format!("`{uri}` is not open")))
+        };
+        match method {
+            "initialize" => Ok (json!({
+                "capabilities": {
+                    "textDocumentSync": { "openClose": true, "change": 2 },
+                    "documentSymbolProvider": true,
+                    "semanticTokensProvider": {
+                        "legend": {
+                            "tokenTypes": self.language.token_types(),
+                            "tokenModifiers": [],
+                        },
+                        "full": true,
+                    },
+                },
+                "serverInfo": { "name": self.language.name() },
+            })),
+            "shutdown" => Ok (Value::Null),
+            "textDocument/documentSymbol" => {
+                let document: &Document = document()?;
+                Ok (Value::Array (self.symbols(document, &document.analysis.tree)))
+            }
+            "textDocument/semanticTokens/full"
+                => Ok (json!({ "data": self.semantic_tokens(document()?) })),
+            _ => Err ((METHOD_NOT_FOUND, format!("`{method}` is not provided"))),
+        }
+    }
+
+    /// Converts a `Diagnostic` to an LSP diagnostic
+    fn diagnostic(&self, document: &Document, uri: &str, diagnostic: &Diagnostic) -> Value {
+        let related: Vec<Value> = diagnostic.related.iter()
+            .map(|(span, message)| json!({
+                "location": { "uri": uri, "range": document.range(*span) },
+                "message": message,
+            }))
+            .collect();
+        json!({
+            "range": document.range(diagnostic.span),
+            "severity": match diagnostic.severity {
+                Severity::Error => 1,
+                Severity::Warning => 2,
+                Severity::Note => 3,
+                Severity::Help => 4,
+            },
+            "source": self.language.name(),
+            "message": diagnostic.message,
+            "relatedInformation": related,
+        })
+    }
+
+    /// Returns the document symbols for the nodes within a node
+    fn symbols(&self, document: &Document, node: &SyntaxNode) -> Vec<Value> {
+        let mut symbols: Vec<Value> = Vec::new();
+        for child in node.children() {
+            let children: Vec<Value> = self.symbols(document, &child);
+            match self.language.symbol_kind(&child) {
+                Some (kind) => {
+                    let range: Value = document.range(child.span());
+                    symbols.push(json!({
+                        "name": self.language.symbol_name(&child),
+                        "detail": child.kind(),
+                        "kind": kind as u32,
+                        "range": range,
+                        "selectionRange": range,
+                        "children": children,
+                    }));
+                }
+                // the symbols within a node that is not a symbol belong to its parent
+                None => symbols.extend(children),
+            }
+        }
+        symbols
+    }
+
+    /// Returns the semantic tokens of a document, encoded relative to each other
+    fn semantic_tokens(&self, document: &Document) -> Vec<u32> {
+        let types: &[&'static str] = self.language.token_types();
+        let mut data: Vec<u32> = Vec::new();
+        let (mut previous_line, mut previous_character): (usize, usize) = (0, 0);
+        for token in document.analysis.tree.tokens() {
+            let Some (index) = types.iter().position(|kind| *kind == token.kind()) else {
+                continue
+            };
+            for span in line_spans(document, &token) {
+                let (line, character): (usize, usize) = document.position(span.start);
+                let length: usize = document.text.as_str()[span.start..span.end]
+                    .encode_utf16()
+                    .count();
+                if length == 0 { continue }
+                let delta_character: usize = if line == previous_line {
+                    character - previous_character
+                } else { character };
+                data.extend([
+                    (line - previous_line) as u32,
+                    delta_character as u32,
+                    length as u32,
+                    index as u32,
+                    0,
+                ]);
+                (previous_line, previous_character) = (line, character);
+            }
+        }
+        data
+    }
+
+}
+
+/// Splits the span of a token into one span for each line it covers, without `\n` or `\r\n` line
+/// breaks
+fn line_spans(document: &Document, token: &SyntaxToken) -> Vec<Span> {
+    let text: &str = document.text.as_str();
+    // the `\r` of a `\r\n` line break is left out of the line before it
+    let line_span = |start: usize, end: usize| if end > start
+        && text[..end].ends_with('\r')
+        && text[end..].starts_with('\n')
+    { Span::new(start, end - 1) } else { Span::new(start, end) };
+    let span: Span = token.span();
+    let mut spans: Vec<Span> = Vec::new();
+    let mut start: usize = span.start;
+    for &line_start in document.line_starts() {
+        if line_start <= start { continue }
+        if line_start > span.end { break }
+        spans.push(line_span(start, line_start - 1));
+        start = line_start;
+    }
+    spans.push(line_span(start, span.end));
+    spans
+}
+
+/// Creates an error response to a request
+fn error_response(id: Value, code: i64, error: impl ToString) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": error.to_string() } })
+}
+
+/// Publishes diagnostics for a document to the client
+fn publish_diagnostics(writer: &mut impl Write, parameters: Value) -> Result<()> {
+    let notification: Value = json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": parameters,
+    });
+    write_message(writer, &notification)
+}
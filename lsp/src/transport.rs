@@ -0,0 +1,53 @@
+// Copyright Rob Gage 2025
+
+use serde_json::Value;
+use std::io::{
+    BufRead,
+    Error,
+    ErrorKind,
+    Result,
+    Write,
+};
+
+/// Reads a message framed with a `Content-Length` header, returning `None` at the end of the
+/// stream
+///
+/// A message that is not valid JSON is read completely before an `ErrorKind::InvalidData` error is
+/// returned, so the next message can still be read. A message whose `Content-Length` is not a
+/// number cannot be told apart from the messages after it, so an `ErrorKind::Other` error is
+/// returned instead, and no more messages should be read from the stream.
+pub fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut length: Option<usize> = None;
+    let mut line: String = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return if length.is_none() { Ok (None) } else {
+                Err (Error::new(ErrorKind::UnexpectedEof, "message ended in its header"))
+            }
+        }
+        let header: &str = line.trim_end();
+        if header.is_empty() {
+            if length.is_some() { break } else { continue }
+        }
+        if let Some ((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            length = Some (value.trim().parse().map_err(|_| Error::other(
+                format!("invalid content length `{}`, so messages cannot be framed", value.trim()),
+            ))?);
+        }
+    }
+    let mut content: Vec<u8> = vec![0; length.unwrap_or_default()];
+    reader.read_exact(&mut content)?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|error| Error::new(ErrorKind::InvalidData, error))
+}
+
+/// Writes a message framed with a `Content-Length` header
+pub fn write_message(writer: &mut impl Write, message: &Value) -> Result<()> {
+    let content: String = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    writer.flush()
+}
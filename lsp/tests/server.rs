@@ -0,0 +1,312 @@
+// Copyright Rob Gage 2025
+
+use pups_core::{
+    Input,
+    prelude::*,
+};
+use pups_lsp::{
+    Analysis,
+    read_message,
+    Server,
+    write_message,
+};
+use pups_text::prelude::*;
+use serde_json::{
+    json,
+    Value,
+};
+use std::{
+    io::{
+        BufReader,
+        Cursor,
+        ErrorKind,
+        pipe,
+        PipeReader,
+        PipeWriter,
+        Write,
+    },
+    thread::{
+        JoinHandle,
+        spawn,
+    },
+};
+
+
+/// Lifts a text parser, which never emits messages, to a parser that emits diagnostics
+fn lift<'a, O>(
+    parser: impl Parser<'a, O, (), (), Text> + 'a,
+) -> impl Parser<'a, O, (), Diagnostic, Text> {
+    parser.map_messages(|()| unreachable!("text parsers do not emit messages"))
+}
+
+/// Parses `let name = number;` statements with `#` comments, warning about capitalized names
+fn statements<'a>() -> impl Parser<'a, (), (), Diagnostic, Text> {
+    let comment = || {
+        lift(sequenced(token("#"), repeated(not(token("\n"), |_| ()).ignore_then(any()))))
+            .trivia("comment")
+    };
+    let space = || repeated(choice((lift(whitespace()).emit(()), comment().emit(())))).emit(());
+    let name = lift(unicode_identifier())
+        .validate(|name: &str, span, emitter| {
+            if name.starts_with(char::is_uppercase) {
+                emitter.emit(Diagnostic::warning(span, "names should not be capitalized"));
+            }
+        })
+        .syntax_token("variable")
+        .labelled("name");
+    let statement = sequence((
+        lift(token("let")).syntax_token("keyword").emit(()),
+        space(),
+        name,
+        space(),
+        lift(token("=")).syntax_token("operator").emit(()),
+        space(),
+        lift(number()).syntax_token("number").emit(()),
+        space(),
+        lift(token(";")).emit(()),
+    )).emit(()).labelled("statement");
+    preceded(space(), repeated(terminated(statement, space()))).emit(())
+}
+
+/// Parses `name: "string"` entries separated by whitespace, highlighting them with classes
+fn entries<'a>() -> impl Parser<'a, (), (), Diagnostic, Text> {
+    let string = sequenced(token("\""), repeated(not(token("\""), |_| ()).ignore_then(any())))
+        .then_ignore(token("\""));
+    let entry = sequence((
+        lift(unicode_identifier()).highlight(HighlightClass::Identifier).emit(()),
+        lift(token(":")).emit(()),
+        lift(whitespace()).or_not().emit(()),
+        lift(string).highlight(HighlightClass::String).emit(()),
+    )).emit(()).labelled("entry");
+    separated(entry, lift(whitespace())).emit(())
+}
+
+/// Parses any single character
+fn any<'a>() -> impl Parser<'a, (), (), (), Text> {
+    |input: &Text| match input.peek() {
+        Some (_) => {
+            input.advance();
+            Ok (())
+        }
+        None => Err (()),
+    }
+}
+
+/// A client connected to a server running on another thread
+struct Client {
+    reader: BufReader<PipeReader>,
+    server: JoinHandle<std::io::Result<()>>,
+    writer: PipeWriter,
+}
+
+impl Client {
+
+    fn start() -> Self { Self::serving(|text: &Text| Analysis::new(&statements(), text)) }
+
+    fn serving(language: fn(&Text) -> Analysis) -> Self {
+        let (server_reader, writer) = pipe().expect("pipes can be created");
+        let (reader, server_writer) = pipe().expect("pipes can be created");
+        let server: JoinHandle<std::io::Result<()>> = spawn(move || {
+            Server::new(language).serve(BufReader::new(server_reader), server_writer)
+        });
+        Self { reader: BufReader::new(reader), server, writer }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        let message: Value = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&mut self.writer, &message).expect("the server is running");
+    }
+
+    fn receive(&mut self) -> Value {
+        read_message(&mut self.reader).expect("the server is running").expect("a message is sent")
+    }
+
+    fn request(&mut self, id: u64, method: &str, params: Value) -> Value {
+        let message: Value = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+        write_message(&mut self.writer, &message).expect("the server is running");
+        let response: Value = self.receive();
+        assert_eq!(response["id"], id);
+        response
+    }
+
+    fn stop(mut self) {
+        assert_eq!(self.request(99, "shutdown", Value::Null)["result"], Value::Null);
+        self.notify("exit", Value::Null);
+        self.server.join().expect("the server does not panic").expect("the server exits cleanly");
+    }
+
+}
+
+
+#[test]
+fn server_provides_language_features() {
+    let uri: &str = "file:///example.txt";
+    let mut client: Client = Client::start();
+    let initialized: Value = client.request(1, "initialize", json!({ "capabilities": {} }));
+    let legend: &Value = &initialized["result"]["capabilities"]["semanticTokensProvider"]["legend"];
+    let types: Vec<&str> = legend["tokenTypes"].as_array().expect("the legend has types")
+        .iter()
+        .map(|kind| kind.as_str().expect("types are names"))
+        .collect();
+    client.notify("initialized", json!({}));
+    let text: &str = "let a = 1; # one\nlet Bc = 23;\nlet = 4;";
+    client.notify("textDocument/didOpen", json!({
+        "textDocument": { "uri": uri, "languageId": "example", "version": 1, "text": text },
+    }));
+    let published: Value = client.receive();
+    assert_eq!(published["method"], "textDocument/publishDiagnostics");
+    let diagnostics: Vec<(u64, &str, &Value)> = published["params"]["diagnostics"]
+        .as_array()
+        .expect("diagnostics are published")
+        .iter()
+        .map(|diagnostic| (
+            diagnostic["severity"].as_u64().expect("diagnostics have severities"),
+            diagnostic["message"].as_str().expect("diagnostics have messages"),
+            &diagnostic["range"]["start"],
+        ))
+        .collect();
+    assert_eq!(diagnostics, [
+        (2, "names should not be capitalized", &json!({ "line": 1, "character": 4 })),
+        (1, "unexpected input", &json!({ "line": 2, "character": 0 })),
+    ]);
+    let symbols: Value = client.request(2, "textDocument/documentSymbol", json!({
+        "textDocument": { "uri": uri },
+    }));
+    let symbols: &Vec<Value> = symbols["result"].as_array().expect("symbols are returned");
+    let names: Vec<&Value> = symbols.iter().map(|symbol| &symbol["name"]).collect();
+    assert_eq!(names, ["let a = 1;", "let Bc = 23;"]);
+    assert_eq!(symbols[1]["detail"], "statement");
+    assert_eq!(symbols[1]["range"]["end"], json!({ "line": 1, "character": 12 }));
+    assert_eq!(symbols[1]["children"][0]["name"], "Bc");
+    let tokens: Value = client.request(3, "textDocument/semanticTokens/full", json!({
+        "textDocument": { "uri": uri },
+    }));
+    let index = |kind: &str| types.iter().position(|name| *name == kind).expect("type exists");
+    let [keyword, variable, operator, number, comment] =
+        ["keyword", "variable", "operator", "number", "comment"].map(index);
+    assert_eq!(tokens["result"]["data"], json!([
+        0, 0, 3, keyword, 0,
+        0, 4, 1, variable, 0,
+        0, 2, 1, operator, 0,
+        0, 2, 1, number, 0,
+        0, 3, 5, comment, 0,
+        1, 0, 3, keyword, 0,
+        0, 4, 2, variable, 0,
+        0, 3, 1, operator, 0,
+        0, 2, 2, number, 0,
+    ]));
+    // fixing the last statement with an incremental change clears its error
+    client.notify("textDocument/didChange", json!({
+        "textDocument": { "uri": uri, "version": 2 },
+        "contentChanges": [
+            { "range": {
+                "start": { "line": 1, "character": 4 },
+                "end": { "line": 1, "character": 6 },
+            }, "text": "bc" },
+            { "range": {
+                "start": { "line": 2, "character": 3 },
+                "end": { "line": 2, "character": 3 },
+            }, "text": " d" },
+        ],
+    }));
+    let published: Value = client.receive();
+    assert_eq!(published["params"]["diagnostics"], json!([]));
+    let symbols: Value = client.request(4, "textDocument/documentSymbol", json!({
+        "textDocument": { "uri": uri },
+    }));
+    assert_eq!(symbols["result"][2]["name"], "let d = 4;");
+    client.stop();
+}
+
+#[test]
+fn server_provides_semantic_tokens_for_highlight_classes() {
+    let uri: &str = "file:///entries.txt";
+    let mut client: Client = Client::serving(|text: &Text| Analysis::new(&entries(), text));
+    let initialized: Value = client.request(1, "initialize", json!({ "capabilities": {} }));
+    let legend: &Value = &initialized["result"]["capabilities"]["semanticTokensProvider"]["legend"];
+    let types: Vec<&str> = legend["tokenTypes"].as_array().expect("the legend has types")
+        .iter()
+        .map(|kind| kind.as_str().expect("types are names"))
+        .collect();
+    client.notify("initialized", json!({}));
+    let text: &str = "a: \"one\"\nbc:\"two\"";
+    client.notify("textDocument/didOpen", json!({
+        "textDocument": { "uri": uri, "languageId": "entries", "version": 1, "text": text },
+    }));
+    let published: Value = client.receive();
+    assert_eq!(published["params"]["diagnostics"], json!([]));
+    let tokens: Value = client.request(2, "textDocument/semanticTokens/full", json!({
+        "textDocument": { "uri": uri },
+    }));
+    let index = |class: HighlightClass| types.iter()
+        .position(|name| *name == class.kind())
+        .expect("highlight classes are in the legend");
+    let [variable, string] = [HighlightClass::Identifier, HighlightClass::String].map(index);
+    assert_eq!(tokens["result"]["data"], json!([
+        0, 0, 1, variable, 0,
+        0, 3, 5, string, 0,
+        1, 0, 2, variable, 0,
+        0, 3, 5, string, 0,
+    ]));
+    client.stop();
+}
+
+#[test]
+fn server_leaves_crlf_line_breaks_out_of_semantic_tokens() {
+    let uri: &str = "file:///crlf.txt";
+    let mut client: Client = Client::start();
+    client.request(1, "initialize", json!({ "capabilities": {} }));
+    client.notify("initialized", json!({}));
+    let text: &str = "let a = 1; # one\r\nlet b = 2;\r\n";
+    client.notify("textDocument/didOpen", json!({
+        "textDocument": { "uri": uri, "languageId": "example", "version": 1, "text": text },
+    }));
+    let published: Value = client.receive();
+    assert_eq!(published["params"]["diagnostics"], json!([]));
+    let tokens: Value = client.request(2, "textDocument/semanticTokens/full", json!({
+        "textDocument": { "uri": uri },
+    }));
+    let lengths: Vec<u64> = tokens["result"]["data"].as_array().expect("tokens are returned")
+        .chunks(5)
+        .map(|token| token[2].as_u64().expect("tokens have lengths"))
+        .collect();
+    assert_eq!(lengths, [3, 1, 1, 1, 5, 3, 1, 1, 1]);
+    client.stop();
+}
+
+#[test]
+fn server_reports_protocol_errors() {
+    let mut client: Client = Client::start();
+    let unknown: Value = client.request(1, "textDocument/hover", json!({}));
+    assert_eq!(unknown["error"]["code"], -32601);
+    let closed: Value = client.request(2, "textDocument/documentSymbol", json!({
+        "textDocument": { "uri": "file:///missing.txt" },
+    }));
+    assert_eq!(closed["error"]["code"], -32602);
+    client.stop();
+}
+
+#[test]
+fn messages_after_invalid_json_are_still_read() {
+    let stream: &str = "Content-Length: 3\r\n\r\n{]}Content-Length: 2\r\n\r\n[]";
+    let mut reader: Cursor<&str> = Cursor::new(stream);
+    let invalid: std::io::Error = read_message(&mut reader).expect_err("the content is not JSON");
+    assert_eq!(invalid.kind(), ErrorKind::InvalidData);
+    assert_eq!(read_message(&mut reader).expect("the message is framed"), Some (json!([])));
+}
+
+#[test]
+fn server_stops_when_a_message_cannot_be_framed() {
+    let mut client: Client = Client::start();
+    let request: &str = r#"{"jsonrpc":"2.0","id":1,"method":"shutdown"}"#;
+    write!(client.writer, "Content-Length: many\r\n\r\n{request}").expect("the server is running");
+    let error: std::io::Error = client.server.join()
+        .expect("the server does not panic")
+        .expect_err("the server stops instead of reading the body as a header");
+    assert_eq!(error.kind(), ErrorKind::Other);
+}
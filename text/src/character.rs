@@ -1,4 +1,4 @@
-/// Copyright Rob Gage 2025
+// Copyright Rob Gage 2025
 
 use unicode_ident::{
     is_xid_continue,
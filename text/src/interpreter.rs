@@ -0,0 +1,443 @@
+// Copyright Rob Gage 2025
+
+use crate::{
+    newline,
+    number,
+    Text,
+    TextInput,
+    token,
+    unicode_identifier,
+    whitespace,
+};
+use pups_core::{
+    choice,
+    choice_dyn,
+    Combinators,
+    delimited,
+    Diagnostic,
+    end,
+    Grammar,
+    implement_modes,
+    Input,
+    lookahead,
+    Mode,
+    ModeResult::{
+        self,
+        Failure,
+        Success,
+    },
+    not,
+    optional,
+    Parser,
+    preceded,
+    repeated,
+    separated_at_least,
+    sequence,
+    sequenced,
+    Span,
+    try_map,
+};
+use std::rc::{
+    Rc,
+    Weak,
+};
+
+/// The names of the parsers that rules can refer to without defining them, with `_` for optional
+/// whitespace
+const BUILT_IN: [&str; 6] = ["_", "end", "identifier", "newline", "number", "whitespace"];
+
+
+/// A generic tree of the rules that matched some input
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseTree {
+    /// The trees of the rules that matched within the rule, in order
+    pub children: Vec<ParseTree>,
+    /// The name of the rule
+    pub rule: String,
+    /// The span of input that the rule matched
+    pub span: Span,
+}
+
+/// A grammar that is loaded from text when a program runs, which creates parsers that output
+/// `ParseTree`s
+///
+/// Grammars are written as rules like `name = expression ;`, where `<-` or `::=` can be used
+/// instead of `=`, and the `;` is optional. Expressions are written with:
+/// - `"..."` or `'...'` for a token, with `\` escaping the next character
+/// - `name` for a rule, or for one of the built-in parsers `end`, `identifier`, `newline`,
+///   `number`, and `whitespace`
+/// - `_` for optional whitespace
+/// - `a b` or `a , b` for a sequence, and `a / b` or `a | b` for alternatives tried in order
+/// - `a*`, `a+`, and `a?` for repetitions and optional input, or `{ a }` and `[ a ]` in EBNF
+/// - `&a` and `!a` for input that must or must not follow, without consuming it
+/// - `( a )` for grouping
+///
+/// Comments start with `#` and end at the end of the line, or are written as `(* ... *)`.
+pub struct RuntimeGrammar {
+    /// The rules of the grammar, with the spans of their names
+    rules: Vec<(String, Span, Expression)>,
+}
+
+impl RuntimeGrammar {
+
+    /// Loads a grammar from text, returning diagnostics for its problems if it is invalid
+    pub fn load(source: &str) -> Result<Self, Vec<Diagnostic>> {
+        let text: Text = Text::from_string(source);
+        let rules: Vec<(String, Span, Expression)> = preceded(gap(), repeated(rule))
+            .parse(&text)
+            .expect("repetitions always succeed");
+        let position: usize = text.store_cursor();
+        if position < source.len() {
+            let span: Span = Span::new(position, position + 1);
+            return Err (vec![Diagnostic::error(span, "expected a rule")])
+        }
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        if rules.is_empty() {
+            diagnostics.push(Diagnostic::error(Span::new(0, 0), "a grammar needs a rule"));
+        }
+        for (index, (name, span, body)) in rules.iter().enumerate() {
+            if rules[..index].iter().any(|(defined, _, _)| defined == name) {
+                diagnostics.push(Diagnostic::error(*span, format!("`{name}` is already defined")));
+            }
+            body.check_references(&rules, &mut diagnostics);
+        }
+        if diagnostics.is_empty() { Ok (Self { rules }) } else { Err (diagnostics) }
+    }
+
+    /// The names of the rules of this grammar, in order
+    pub fn rules(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|(name, _, _)| name.as_str())
+    }
+
+    /// Creates a parser for a rule of this grammar, returning `None` if there is no such rule
+    pub fn parser<'a>(&self, rule: &str)
+        -> Option<Box<dyn Parser<'a, ParseTree, (), (), Text> + 'a>>
+    {
+        let index: usize = self.rules.iter().position(|(name, _, _)| name == rule)?;
+        // rules refer to each other weakly so recursive rules do not keep each other alive
+        let rules: Rc<Vec<Rule<'a>>> = Rc::new_cyclic(|table: &Weak<Vec<Rule<'a>>>| {
+            self.rules.iter().map(|(name, _, body)| Rule {
+                name: name.clone(),
+                parser: body.compile(&self.rules, table),
+            }).collect()
+        });
+        let reference: RuleReference<'a> = RuleReference { index, rules: Rc::downgrade(&rules) };
+        Some (Box::new(Entry { reference, _rules: rules }))
+    }
+
+}
+
+
+/// An expression of a `RuntimeGrammar`
+enum Expression {
+    /// Input that must follow, without being consumed
+    And (Box<Expression>),
+    /// Alternatives that are tried in order
+    Choice (Vec<Expression>),
+    /// A token
+    Literal (String),
+    /// Input that must not follow
+    Not (Box<Expression>),
+    /// Input that may be absent
+    Optional (Box<Expression>),
+    /// A rule or a built-in parser, with the span of its name
+    Reference (String, Span),
+    /// Input that is repeated a minimum number of times
+    Repeated (Box<Expression>, usize),
+    /// Input that appears in order
+    Sequence (Vec<Expression>),
+}
+
+impl Expression {
+
+    /// Adds a diagnostic for every reference in this expression that is not to a rule or a
+    /// built-in parser
+    fn check_references(
+        &self,
+        rules: &[(String, Span, Expression)],
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        match self {
+            Expression::And (expression)
+            | Expression::Not (expression)
+            | Expression::Optional (expression)
+            | Expression::Repeated (expression, _)
+                => expression.check_references(rules, diagnostics),
+            Expression::Choice (expressions) | Expression::Sequence (expressions)
+                => for expression in expressions {
+                    expression.check_references(rules, diagnostics)
+                },
+            Expression::Literal (_) => {}
+            Expression::Reference (name, span) => {
+                let defined: bool = rules.iter().any(|(rule, _, _)| rule == name);
+                if !defined && !BUILT_IN.contains(&name.as_str()) {
+                    diagnostics.push(Diagnostic::error(*span, format!("undefined rule `{name}`")));
+                }
+            }
+        }
+    }
+
+    /// Compiles this expression into a parser that outputs the trees of the rules it matches
+    fn compile<'a>(
+        &self,
+        rules: &[(String, Span, Expression)],
+        table: &Weak<Vec<Rule<'a>>>,
+    ) -> Compiled<'a> {
+        match self {
+            Expression::And (expression)
+                => Box::new(lookahead(expression.compile(rules, table)).map(|_| Vec::new())),
+            Expression::Choice (alternatives) => Box::new(choice_dyn(
+                alternatives.iter().map(|alternative| alternative.compile(rules, table)).collect()
+            ).expect("choices are only built from alternatives")),
+            Expression::Literal (lexeme) => Box::new(Literal (lexeme.clone())),
+            Expression::Not (expression)
+                => Box::new(not(expression.compile(rules, table), |_| ()).map(|()| Vec::new())),
+            Expression::Optional (expression) => Box::new(
+                optional(expression.compile(rules, table)).map(Option::unwrap_or_default)
+            ),
+            Expression::Reference (name, _) => {
+                if let Some (index) = rules.iter().position(|(rule, _, _)| rule == name) {
+                    return Box::new(RuleReference { index, rules: table.clone() })
+                }
+                built_in(name)
+            }
+            Expression::Repeated (expression, minimum) => Box::new(
+                repeated(expression.compile(rules, table))
+                    .at_least(*minimum)
+                    .map(|trees: Vec<Vec<ParseTree>>| trees.concat())
+            ),
+            Expression::Sequence (items) => items.iter()
+                .map(|item| item.compile(rules, table))
+                .reduce(|sequence, item| {
+                    Box::new(sequenced(sequence, item).map(|(mut trees, rest)| {
+                        trees.extend(rest);
+                        trees
+                    }))
+                })
+                .expect("sequences have at least one item"),
+        }
+    }
+
+}
+
+/// A parser that is compiled from an `Expression`
+type Compiled<'a> = Box<dyn Parser<'a, Vec<ParseTree>, (), (), Text> + 'a>;
+
+/// Creates the parser for a built-in parser name
+fn built_in<'a>(name: &str) -> Compiled<'a> {
+    match name {
+        "_" => Box::new(optional(whitespace::<_, Text>()).map(|_| Vec::new())),
+        "end" => Box::new(end::<Text>().map(|()| Vec::new())),
+        "identifier" => Box::new(unicode_identifier::<_, Text>().map(|_| Vec::new())),
+        "newline" => Box::new(newline::<_, Text>().map(|()| Vec::new())),
+        "number" => Box::new(number::<_, Text>().map(|_| Vec::new())),
+        "whitespace" => Box::new(whitespace::<_, Text>().map(|_| Vec::new())),
+        _ => unreachable!("references are checked when the grammar is loaded"),
+    }
+}
+
+
+/// A compiled rule of a `RuntimeGrammar`
+struct Rule<'a> {
+    /// The name of the rule
+    name: String,
+    /// The parser for the body of the rule
+    parser: Compiled<'a>,
+}
+
+/// A parser that applies a rule, outputting its tree
+struct RuleReference<'a> {
+    /// The index of the rule
+    index: usize,
+    /// The compiled rules of the grammar
+    rules: Weak<Vec<Rule<'a>>>,
+}
+
+impl<'a> Parser<'a, Vec<ParseTree>, (), (), Text> for RuleReference<'a> {
+
+    fn apply<_Mode: Mode>(&self, input: &'a Text) -> ModeResult<Vec<ParseTree>, (), (), _Mode> {
+        let rules: Rc<Vec<Rule<'a>>> = self.rules.upgrade()
+            .expect("the rules of a grammar live as long as the parser that applies them");
+        let rule: &Rule<'a> = &rules[self.index];
+        let start: usize = input.store_cursor();
+        let result: ModeResult<Vec<ParseTree>, (), (), _Mode>
+            = _Mode::apply_nested(&rule.parser, input);
+        let span: Span = Span::new(start, input.store_cursor());
+        _Mode::map_output(result, |children| vec![ParseTree {
+            children,
+            rule: rule.name.clone(),
+            span,
+        }])
+    }
+
+    implement_modes!('a, Vec<ParseTree>, (), (), Text);
+
+}
+
+/// The parser for the entry rule of a grammar, which keeps the compiled rules alive
+struct Entry<'a> {
+    /// The reference to the entry rule
+    reference: RuleReference<'a>,
+    /// The compiled rules of the grammar
+    _rules: Rc<Vec<Rule<'a>>>,
+}
+
+impl<'a> Parser<'a, ParseTree, (), (), Text> for Entry<'a> {
+
+    fn apply<_Mode: Mode>(&self, input: &'a Text) -> ModeResult<ParseTree, (), (), _Mode> {
+        _Mode::map_output(_Mode::apply_nested(&self.reference, input), |mut trees| trees.remove(0))
+    }
+
+    implement_modes!('a, ParseTree, (), (), Text);
+
+}
+
+/// A parser for a token that is only known when a program runs
+struct Literal (String);
+
+impl<'a> Parser<'a, Vec<ParseTree>, (), (), Text> for Literal {
+
+    fn apply<_Mode: Mode>(&self, input: &'a Text) -> ModeResult<Vec<ParseTree>, (), (), _Mode> {
+        if input.starts_with(&self.0) {
+            input.skip_bytes(self.0.len());
+            Success (_Mode::convert_output(Vec::new()), _Mode::new_message_container())
+        } else {
+            Failure (_Mode::convert_error(()), _Mode::new_message_container())
+        }
+    }
+
+    implement_modes!('a, Vec<ParseTree>, (), (), Text);
+
+    fn describe(&self) -> Grammar { Grammar::terminal(self.0.clone()) }
+
+}
+
+
+/// Parses a rule, like `name = expression ;`
+fn rule(input: &Text) -> Result<(String, Span, Expression), ()> {
+    sequence((
+        name(),
+        preceded(gap(), assignment()),
+        preceded(gap(), alternatives),
+        optional(preceded(gap(), token(";"))),
+        gap(),
+    ))
+        .map(|((name, span), _, body, _, _)| (name, span, body))
+        .parse(input)
+}
+
+/// Parses alternatives, like `a / b`
+fn alternatives(input: &Text) -> Result<Expression, ()> {
+    let bar = choice((token("/"), token("|")));
+    separated_at_least(items, delimited(gap(), bar, gap()), 1)
+        .map(|mut alternatives: Vec<Expression>| match alternatives.len() {
+            1 => alternatives.remove(0),
+            _ => Expression::Choice (alternatives),
+        })
+        .parse(input)
+}
+
+/// Parses a sequence, like `a b` or `a , b`
+fn items(input: &Text) -> Result<Expression, ()> {
+    let comma = sequenced(optional(preceded(gap(), token(","))), gap());
+    separated_at_least(prefixed, comma, 1)
+        .map(|mut items: Vec<Expression>| match items.len() {
+            1 => items.remove(0),
+            _ => Expression::Sequence (items),
+        })
+        .parse(input)
+}
+
+/// Parses an expression that may be preceded by `&` or `!`
+fn prefixed(input: &Text) -> Result<Expression, ()> {
+    choice((
+        preceded(sequenced(token("&"), gap()), suffixed)
+            .map(|expression| Expression::And (Box::new(expression))),
+        preceded(sequenced(token("!"), gap()), suffixed)
+            .map(|expression| Expression::Not (Box::new(expression))),
+        suffixed,
+    )).parse(input)
+}
+
+/// Parses an expression that may be followed by `*`, `+`, or `?`
+fn suffixed(input: &Text) -> Result<Expression, ()> {
+    let suffix = preceded(gap(), choice((token("*"), token("+"), token("?"))));
+    sequenced(primary, repeated(suffix))
+        .map(|(expression, suffixes): (Expression, Vec<&str>)| {
+            suffixes.into_iter().fold(expression, |expression, suffix| match suffix {
+                "*" => Expression::Repeated (Box::new(expression), 0),
+                "+" => Expression::Repeated (Box::new(expression), 1),
+                _ => Expression::Optional (Box::new(expression)),
+            })
+        })
+        .parse(input)
+}
+
+/// Parses a token, a reference, or a bracketed expression
+fn primary(input: &Text) -> Result<Expression, ()> {
+    let bracketed = |open: &'static str, close: &'static str| {
+        delimited(sequenced(token(open), gap()), alternatives, sequenced(gap(), token(close)))
+    };
+    choice((
+        literal.map(Expression::Literal),
+        // a name followed by an assignment starts the next rule instead
+        preceded(not(sequenced(name(), sequenced(gap(), assignment())), |_| ()), name())
+            .map(|(name, span)| Expression::Reference (name, span)),
+        try_map(token("_"), |_, span| Ok (Expression::Reference ("_".to_string(), span))),
+        bracketed("(", ")"),
+        bracketed("[", "]").map(|expression| Expression::Optional (Box::new(expression))),
+        bracketed("{", "}").map(|expression| Expression::Repeated (Box::new(expression), 0)),
+    )).parse(input)
+}
+
+/// Parses a quoted token, like `"let"` or `'let'`
+fn literal(input: &Text) -> Result<String, ()> {
+    let quoted = |quote: &'static str| {
+        let escaped = preceded(token("\\"), character).map(|escaped| match escaped {
+            'n' => '\n',
+            't' => '\t',
+            escaped => escaped,
+        });
+        delimited(
+            token(quote),
+            repeated(preceded(not(token(quote), |_| ()), choice((escaped, character)))),
+            token(quote),
+        ).map(|characters: Vec<char>| characters.into_iter().collect::<String>())
+    };
+    choice((quoted("\""), quoted("'"))).parse(input)
+}
+
+/// Parses the name of a rule, with its span
+fn name<'a>() -> impl Parser<'a, (String, Span), (), (), Text> {
+    try_map(unicode_identifier(), |name: &str, span| Ok ((name.to_string(), span)))
+}
+
+/// Parses the operator that assigns an expression to a rule
+fn assignment<'a>() -> impl Parser<'a, &'a str, (), (), Text> {
+    choice((token("="), token("<-"), token("::=")))
+}
+
+/// Skips whitespace and comments
+fn gap<'a>() -> impl Parser<'a, (), (), (), Text> {
+    let line_comment = preceded(
+        token("#"),
+        repeated(preceded(not(token("\n"), |_| ()), character)),
+    );
+    let block_comment = delimited(
+        token("(*"),
+        repeated(preceded(not(token("*)"), |_| ()), character)),
+        token("*)"),
+    );
+    repeated(choice((
+        whitespace().map(|_| ()),
+        line_comment.map(|_| ()),
+        block_comment.map(|_| ()),
+    ))).map(|_| ())
+}
+
+/// Parses any character
+fn character(input: &Text) -> Result<char, ()> {
+    let character: char = input.peek().ok_or(())?;
+    input.advance();
+    Ok (character)
+}
@@ -1,7 +1,10 @@
 // Copyright Rob Gage 2025
 
 mod character;
+mod interpreter;
+mod parsable;
 mod text;
+mod text_edit;
 mod parsers;
 mod text_input;
 
@@ -9,10 +12,19 @@ use character::Character;
 use text_input::TextInput;
 
 pub mod prelude {
-    use pups_core::Parser;
     pub use crate::{
+        interpreter::{
+            ParseTree,
+            RuntimeGrammar,
+        },
+        parsable::{
+            parsed,
+            Parsable,
+            Parsed,
+        },
         parsers::*,
         text::Text,
+        text_edit::TextEdit,
     };
 }
 pub use prelude::*;
\ No newline at end of file
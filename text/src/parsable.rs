@@ -0,0 +1,46 @@
+// Copyright Rob Gage 2025
+
+use crate::Text;
+use pups_core::{
+    implement_modes,
+    Mode,
+    ModeResult,
+    Parser,
+};
+use std::marker::PhantomData;
+
+/// Implementors can be parsed from `Text` by a parser that is determined by their type, which is
+/// usually generated with `#[derive(Parse)]`
+pub trait Parsable: Sized {
+
+    /// Applies the parser for this type
+    fn apply<_Mode: Mode>(input: &Text) -> ModeResult<Self, (), (), _Mode>;
+
+    /// The parser for this type
+    fn parser() -> Parsed<Self> { parsed() }
+
+}
+
+impl<T: Parsable> Parsable for Box<T> {
+
+    fn apply<_Mode: Mode>(input: &Text) -> ModeResult<Self, (), (), _Mode> {
+        _Mode::map_output(T::apply::<_Mode>(input), Box::new)
+    }
+
+}
+
+pub struct Parsed<T> (PhantomData<T>);
+
+impl<'a, T: Parsable> Parser<'a, T, (), (), Text> for Parsed<T> {
+
+    fn apply<_Mode: Mode>(&self, input: &'a Text) -> ModeResult<T, (), (), _Mode> {
+        T::apply::<_Mode>(input)
+    }
+
+    implement_modes!('a, T, (), (), Text);
+
+}
+
+/// Parses a type that implements `Parsable`, which allows types to refer to each other, and to
+/// themselves through a `Box`
+pub const fn parsed<T: Parsable>() -> Parsed<T> { Parsed (PhantomData) }
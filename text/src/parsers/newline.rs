@@ -5,6 +5,8 @@ use crate::{
     TextInput
 };
 use pups_core::{
+    Doc,
+    Grammar,
     implement_modes,
     Input,
     Mode,
@@ -13,7 +15,8 @@ use pups_core::{
         Failure,
         Success,
     },
-    Parser
+    Parser,
+    Printer,
 };
 
 struct Newline;
@@ -38,10 +41,20 @@ where
 
     implement_modes!('a, (), (), (), I);
 
+    fn describe(&self) -> Grammar { Grammar::Special ("newline") }
+
+}
+
+impl Printer<()> for Newline {
+
+    fn print(&self, _: &()) -> Option<Doc> { Some (Doc::HardLine) }
+
+    fn print_ignored(&self) -> Option<Doc> { Some (Doc::HardLine) }
+
 }
 
 /// Parses a single newline character
-pub const fn newline<'a, C, I>() -> impl Parser<'a, (), (), (), I>
+pub const fn newline<'a, C, I>() -> impl Parser<'a, (), (), (), I> + Printer<()>
 where
     C: Character,
     I: Input<'a, Item = C> + TextInput,
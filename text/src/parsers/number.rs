@@ -5,6 +5,8 @@ use crate::{
     TextInput
 };
 use pups_core::{
+    Doc,
+    Grammar,
     implement_modes,
     Input,
     Mode,
@@ -13,7 +15,8 @@ use pups_core::{
         Failure,
         Success,
     },
-    Parser
+    Parser,
+    Printer,
 };
 
 /// Parses a number
@@ -48,12 +51,31 @@ where
 
     implement_modes!('a, I::Slice, (), (), I);
 
+    fn describe(&self) -> Grammar { Grammar::Special ("number") }
+
+}
+
+impl<S> Printer<S> for Number
+where
+    S: AsRef<str>,
+{
+
+    fn print(&self, number: &S) -> Option<Doc> {
+        let number: &str = number.as_ref();
+        let valid: bool = !number.is_empty()
+            && number.chars().all(|character| character.is_ascii_decimal());
+        valid.then(|| Doc::text(number))
+    }
+
 }
 
 /// Parses a number composed of ASCII decimal digits 0-9
 pub const fn number<'a, C, I>(
 ) -> impl Parser<'a, I::Slice, (), (), I>
+    + Printer<I::Slice>
+    + Printer<String>
 where
     C: Character,
     I: Input<'a, Item = C> + TextInput,
+    I::Slice: AsRef<str>,
 { Number }
\ No newline at end of file
@@ -2,6 +2,8 @@
 
 use crate::TextInput;
 use pups_core::{
+    Doc,
+    Grammar,
     implement_modes,
     Input,
     Mode,
@@ -10,7 +12,8 @@ use pups_core::{
         Failure,
         Success,
     },
-    Parser
+    Parser,
+    Printer,
 };
 
 struct Token (&'static str);
@@ -38,12 +41,24 @@ where
 
     implement_modes!('a, I::Slice, (), (), I);
 
+    fn describe(&self) -> Grammar { Grammar::terminal(self.0) }
+
+}
+
+impl<S> Printer<S> for Token {
+
+    fn print(&self, _: &S) -> Option<Doc> { Some (Doc::text(self.0)) }
+
+    fn print_ignored(&self) -> Option<Doc> { Some (Doc::text(self.0)) }
+
 }
 
 /// Parses a lexical token
 pub const fn token<'a, I>(
     lexeme: &'static str
 ) -> impl Parser<'a, I::Slice, (), (), I>
+    + Printer<I::Slice>
+    + Printer<String>
 where
     I: Input<'a> + TextInput,
 { Token (lexeme) }
\ No newline at end of file
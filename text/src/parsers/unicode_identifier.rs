@@ -5,6 +5,8 @@ use crate::{
     TextInput
 };
 use pups_core::{
+    Doc,
+    Grammar,
     implement_modes,
     Input,
     Mode,
@@ -13,7 +15,8 @@ use pups_core::{
         Failure,
         Success,
     },
-    Parser
+    Parser,
+    Printer,
 };
 
 /// Parses a unicode identifier
@@ -33,7 +36,6 @@ where
         if let Some (character) = input.peek() && character.is_unicode_identifier_start() {
             input.advance();
             loop {
-                let cursor: usize = input.store_cursor();
                 if let Some (character) = input.peek()
                     && character.is_unicode_identifier_continuation() {
                     input.advance();
@@ -48,12 +50,32 @@ where
 
     implement_modes!('a, I::Slice, (), (), I);
 
+    fn describe(&self) -> Grammar { Grammar::Special ("identifier") }
+
+}
+
+impl<S> Printer<S> for UnicodeIdentifier
+where
+    S: AsRef<str>,
+{
+
+    fn print(&self, identifier: &S) -> Option<Doc> {
+        let mut characters = identifier.as_ref().chars();
+        let valid: bool = characters.next()
+            .is_some_and(|character| character.is_unicode_identifier_start())
+            && characters.all(|character| character.is_unicode_identifier_continuation());
+        valid.then(|| Doc::text(identifier.as_ref()))
+    }
+
 }
 
 /// Parses a unicode identifier
 pub const fn unicode_identifier<'a, C, I>(
 ) -> impl Parser<'a, I::Slice, (), (), I>
+    + Printer<I::Slice>
+    + Printer<String>
 where
     C: Character,
     I: Input<'a, Item = C> + TextInput,
+    I::Slice: AsRef<str>,
 { UnicodeIdentifier }
\ No newline at end of file
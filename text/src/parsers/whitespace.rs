@@ -5,6 +5,8 @@ use crate::{
     TextInput
 };
 use pups_core::{
+    Doc,
+    Grammar,
     implement_modes,
     Input,
     Mode,
@@ -13,7 +15,8 @@ use pups_core::{
         Failure,
         Success,
     },
-    Parser
+    Parser,
+    Printer,
 };
 
 /// Parses whitespace
@@ -49,10 +52,23 @@ where
 
     implement_modes!('a, I::Slice, (), (), I);
 
+    fn describe(&self) -> Grammar { Grammar::Special ("whitespace") }
+
+}
+
+// whitespace is laid out by the printer rather than kept as it was parsed
+impl<S> Printer<S> for Whitespace {
+
+    fn print(&self, _: &S) -> Option<Doc> { Some (Doc::line()) }
+
+    fn print_ignored(&self) -> Option<Doc> { Some (Doc::line()) }
+
 }
 
 /// Parses whitespace
 pub const fn whitespace<'a, C, I>() -> impl Parser<'a, I::Slice, (), (), I>
+    + Printer<I::Slice>
+    + Printer<String>
 where
     C: Character,
     I: Input<'a, Item = C> + TextInput,
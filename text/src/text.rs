@@ -2,13 +2,14 @@
 
 use crate::{
     Character,
+    TextEdit,
     TextInput,
 };
-use pups_core::Input;
-use std::{
-    cell::UnsafeCell,
-    marker::PhantomData,
+use pups_core::{
+    Input,
+    MemoTable,
 };
+use std::cell::UnsafeCell;
 
 /// UTF-8 text that can be consumed by parsers
 pub struct Text {
@@ -16,15 +17,35 @@ pub struct Text {
     buffer: String,
     /// The byte offset in the buffer that represents the start of the `Text`
     byte_offset: UnsafeCell<usize>,
+    /// The results of memoized parsers applied to the `Text`
+    memo_table: MemoTable,
 }
 
 impl<'a> Text {
 
     /// Creates a new `Text` from a `&str`
     pub fn from_string(string: &'a str) -> Self {
-        Self { buffer: string.to_string(), byte_offset: UnsafeCell::new(0) }
+        Self {
+            buffer: string.to_string(),
+            byte_offset: UnsafeCell::new(0),
+            memo_table: MemoTable::new(),
+        }
+    }
+
+    /// Edits this `Text`, keeping the results of memoized parsers that do not depend on the edited
+    /// span so they are reused when it is parsed again, and moving the cursor to the start
+    ///
+    /// Panics if the span is out of bounds or does not lie on character boundaries.
+    pub fn apply_edit(&mut self, edit: &TextEdit) {
+        let (start, end): (usize, usize) = (edit.span.start, edit.span.end);
+        self.buffer.replace_range(start..end, &edit.replacement);
+        self.memo_table.edit(edit.span, edit.replacement.len());
+        *self.byte_offset.get_mut() = 0;
     }
 
+    /// The text stored in this `Text`
+    pub fn as_str(&self) -> &str { &self.buffer }
+
 }
 
 impl<'a> Input<'a> for Text {
@@ -41,7 +62,10 @@ impl<'a> Input<'a> for Text {
 
     fn peek(&self) -> Option<Self::Item> {
         let byte_offset: usize = unsafe { *self.byte_offset.get() };
-        self.buffer[byte_offset..].chars().next()
+        let character: Option<char> = self.buffer[byte_offset..].chars().next();
+        // looking past the end also depends on the input, since text could be added there
+        self.memo_table.examine(byte_offset + character.map_or(1, char::len_utf8));
+        character
     }
 
     fn slice(&'a self, start: usize, end: usize) -> &'a str { &self.buffer[start..end] }
@@ -50,12 +74,15 @@ impl<'a> Input<'a> for Text {
 
     fn store_cursor(&self) -> usize { unsafe { *self.byte_offset.get() } }
 
+    fn memo_table(&self) -> Option<&MemoTable> { Some (&self.memo_table) }
+
 }
 
-impl<'a> TextInput for Text {
+impl TextInput for Text {
 
     fn starts_with(&self, string: &str) -> bool {
         let byte_offset: usize = unsafe { *self.byte_offset.get() };
+        self.memo_table.examine((byte_offset + string.len()).min(self.buffer.len() + 1));
         self.buffer[byte_offset..].starts_with(string)
     }
 
@@ -0,0 +1,29 @@
+// Copyright Rob Gage 2025
+
+use pups_core::Span;
+
+/// A change to a `Text` that replaces the text in a span of byte offsets
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TextEdit {
+    /// The text that replaces the span
+    pub replacement: String,
+    /// The span of byte offsets that is replaced
+    pub span: Span,
+}
+
+impl TextEdit {
+
+    /// Creates a new `TextEdit` that replaces a span with some text
+    pub fn new(span: Span, replacement: impl Into<String>) -> Self {
+        Self { replacement: replacement.into(), span }
+    }
+
+    /// Creates a new `TextEdit` that deletes a span
+    pub fn delete(span: Span) -> Self { Self::new(span, "") }
+
+    /// Creates a new `TextEdit` that inserts text at a byte offset
+    pub fn insert(byte_offset: usize, text: impl Into<String>) -> Self {
+        Self::new(Span::new(byte_offset, byte_offset), text)
+    }
+
+}
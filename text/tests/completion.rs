@@ -0,0 +1,142 @@
+// Copyright Rob Gage 2025
+
+use pups_core::{
+    implement_modes,
+    Input,
+    Mode,
+    prelude::*,
+};
+use pups_text::prelude::*;
+use std::cell::Cell;
+
+
+/// Parses `let name = value;` and `print value;` statements, where values are names or numbers
+fn statements<'a>() -> impl Parser<'a, (), (), (), Text> {
+    let space = || whitespace().or_not();
+    let name = || unicode_identifier().labelled("name");
+    let value = || choice((number(), name())).labelled("value");
+    let assignment = sequence((
+        token("let"),
+        space(),
+        name(),
+        space(),
+        token("="),
+        space(),
+        value(),
+    )).emit(());
+    let print = sequence((token("print"), space(), value())).emit(());
+    let statement = sequenced(choice((assignment, print)), sequenced(space(), token(";")))
+        .labelled("statement");
+    repeated(sequenced(space(), statement)).emit(())
+}
+
+/// A parser that counts how many times it is described
+struct Counted<'c, P> {
+    /// The number of times the parser was described
+    described: &'c Cell<usize>,
+    /// The parser that is counted
+    parser: P,
+}
+
+impl<'a, P> Parser<'a, &'a str, (), (), Text> for Counted<'_, P>
+where
+    P: Parser<'a, &'a str, (), (), Text>,
+{
+
+    fn apply<_Mode: Mode>(&self, input: &'a Text) -> ModeResult<&'a str, (), (), _Mode> {
+        _Mode::apply_nested(&self.parser, input)
+    }
+
+    implement_modes!('a, &'a str, (), (), Text);
+
+    fn describe(&self) -> Grammar {
+        self.described.set(self.described.get() + 1);
+        self.parser.describe()
+    }
+
+}
+
+/// Returns what is expected at an offset of some text
+fn expected_at(text: &str, offset: usize) -> Vec<Expected> {
+    let text: Text = Text::from_string(text);
+    Completion::run(&statements(), &text, offset)
+}
+
+
+#[test]
+fn completion_collects_every_alternative_alive_at_the_offset() {
+    assert_eq!(expected_at("", 0), [
+        Expected::Special ("whitespace"),
+        Expected::Label ("statement"),
+        Expected::Token ("let".into()),
+        Expected::Token ("print".into()),
+    ]);
+    assert_eq!(expected_at("let x = 1; print x;", 17), [
+        Expected::Label ("value"),
+        Expected::Special ("number"),
+        Expected::Label ("name"),
+        Expected::Special ("identifier"),
+    ]);
+    assert_eq!(expected_at("let x", 5), [
+        Expected::Special ("whitespace"),
+        Expected::Token ("=".into()),
+    ]);
+}
+
+#[test]
+fn completion_ignores_alternatives_that_failed_before_the_offset() {
+    assert_eq!(expected_at("print 1 +", 9), []);
+    assert_eq!(expected_at("let x = 1; let", 10), [
+        Expected::Special ("whitespace"),
+        Expected::Label ("statement"),
+        Expected::Token ("let".into()),
+        Expected::Token ("print".into()),
+    ]);
+}
+
+#[test]
+fn completion_describes_nested_parsers_once() {
+    let described: Cell<usize> = Cell::new(0);
+    let text: Text = Text::from_string("");
+    let parser = Counted { described: &described, parser: token("x") }
+        .labelled("a")
+        .labelled("b")
+        .labelled("c")
+        .labelled("d");
+    assert_eq!(Completion::run(&parser, &text, 0), [
+        Expected::Label ("d"),
+        Expected::Label ("c"),
+        Expected::Label ("b"),
+        Expected::Label ("a"),
+        Expected::Token ("x".into()),
+    ]);
+    assert_eq!(described.get(), 1);
+}
+
+#[test]
+fn completion_is_not_answered_by_results_remembered_while_parsing() {
+    let text: Text = Text::from_string("let x = 1; ");
+    let parser = statements().memoize("statements");
+    assert_eq!(parser.parse(&text), Ok (()));
+    text.move_cursor(0);
+    assert_eq!(Completion::run(&parser, &text, 11), [
+        Expected::Label ("statement"),
+        Expected::Token ("let".into()),
+        Expected::Token ("print".into()),
+    ]);
+}
+
+#[test]
+fn completion_lists_what_several_alternatives_expect_once() {
+    let text: Text = Text::from_string("");
+    let parser = choice((
+        sequenced(token("let"), token("x")).emit(()),
+        sequenced(token("let"), token("y")).emit(()),
+        token("print").emit(()),
+        token("let").emit(()),
+    ));
+    assert_eq!(Completion::run(&parser, &text, 0), [
+        Expected::Token ("let".into()),
+        Expected::Token ("print".into()),
+    ]);
+}
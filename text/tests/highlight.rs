@@ -0,0 +1,104 @@
+// Copyright Rob Gage 2025
+
+use pups_core::{
+    Input,
+    prelude::*,
+};
+use pups_text::prelude::*;
+
+
+/// Parses any single character
+fn any<'a>() -> impl Parser<'a, (), (), (), Text> {
+    |input: &Text| match input.peek() {
+        Some (_) => {
+            input.advance();
+            Ok (())
+        }
+        None => Err (()),
+    }
+}
+
+/// Parses `let name = value;` statements with `#` comments, where values are numbers or strings,
+/// skipping invalid statements up to the next `;`
+fn statements<'a>() -> impl Parser<'a, (), (), (), Text> {
+    let until = |end: &'static str| repeated(not(token(end), |_| ()).ignore_then(any())).emit(());
+    let comment = move || sequenced(token("#"), until("\n")).highlight(HighlightClass::Comment);
+    let space = || repeated(choice((whitespace().emit(()), comment().emit(())))).emit(());
+    let string = delimited(token("\""), until("\""), token("\""))
+        .highlight(HighlightClass::String);
+    let statement = sequence((
+        token("let").highlight(HighlightClass::Keyword),
+        space(),
+        unicode_identifier().highlight(HighlightClass::Identifier),
+        space(),
+        token("="),
+        space(),
+        choice((number().highlight(HighlightClass::Number).emit(()), string)),
+        space(),
+        token(";"),
+    )).emit(()).catch(sequenced(until(";"), token(";")).emit(()));
+    terminated(repeated(preceded(space(), statement)), space()).emit(())
+}
+
+/// Returns the highlighted text of some source code
+fn highlights(source: &str) -> Vec<(&str, HighlightClass)> {
+    let text: Text = Text::from_string(source);
+    Highlight::run(&statements(), &text).into_iter()
+        .map(|(span, class)| (&source[span.start..span.end], class))
+        .collect()
+}
+
+
+#[test]
+fn highlights_are_flat_and_in_order() {
+    assert_eq!(highlights("# greeting\nlet x = \"hi\";\nlet y = 2;"), [
+        ("# greeting", HighlightClass::Comment),
+        ("let", HighlightClass::Keyword),
+        ("x", HighlightClass::Identifier),
+        ("\"hi\"", HighlightClass::String),
+        ("let", HighlightClass::Keyword),
+        ("y", HighlightClass::Identifier),
+        ("2", HighlightClass::Number),
+    ]);
+}
+
+#[test]
+fn highlights_survive_invalid_input_through_recovery() {
+    assert_eq!(highlights("let a = 1; let b = ; let c = \"d\"; # end"), [
+        ("let", HighlightClass::Keyword),
+        ("a", HighlightClass::Identifier),
+        ("1", HighlightClass::Number),
+        ("let", HighlightClass::Keyword),
+        ("c", HighlightClass::Identifier),
+        ("\"d\"", HighlightClass::String),
+        ("# end", HighlightClass::Comment),
+    ]);
+}
+
+#[test]
+fn highlights_of_abandoned_attempts_are_not_repeated() {
+    let source: &str = "let x";
+    let text: Text = Text::from_string(source);
+    let keyword = || token("let").highlight(HighlightClass::Keyword);
+    let parser = sequence((
+        lookahead(keyword()),
+        choice((
+            sequence((keyword(), whitespace(), token("y"))).emit(()),
+            sequence((keyword(), whitespace(), token("x").highlight(HighlightClass::Identifier)))
+                .emit(()),
+        )),
+    ));
+    let highlighted: Vec<(&str, HighlightClass)> = Highlight::run(&parser, &text).into_iter()
+        .map(|(span, class)| (&source[span.start..span.end], class))
+        .collect();
+    assert_eq!(highlighted, [("let", HighlightClass::Keyword), ("x", HighlightClass::Identifier)]);
+    text.move_cursor(0);
+    let (_, tree) = Cst::run(&parser, &text);
+    let tokens: Vec<(&str, HighlightClass)> = tree.tokens().into_iter()
+        .filter_map(|token| Some ((
+            &source[token.span().start..token.span().end],
+            HighlightClass::from_kind(token.kind())?,
+        )))
+        .collect();
+    assert_eq!(tokens, highlighted);
+}
@@ -0,0 +1,79 @@
+// Copyright Rob Gage 2025
+
+use pups_core::prelude::*;
+use pups_text::prelude::*;
+use std::cell::Cell;
+
+
+/// Parses `name = number;` assignments, memoizing each one and counting how many are parsed anew
+fn assignments<'a>(
+    parsed: &'a Cell<usize>,
+) -> impl Parser<'a, Vec<(String, String)>, (), (), Text> {
+    let space = || whitespace().or_not();
+    let assignment = sequence((
+        space(),
+        unicode_identifier(),
+        space(),
+        token("="),
+        space(),
+        number(),
+        space(),
+        token(";"),
+    )).map(move |(_, name, _, _, _, value, _, _): (_, &str, _, _, _, &str, _, _)| {
+        parsed.set(parsed.get() + 1);
+        (name.to_string(), value.to_string())
+    });
+    terminated(repeated(assignment.memoize("assignment")), sequenced(space(), end()))
+}
+
+/// Generates pseudo-random numbers
+struct Random (u64);
+
+impl Random {
+
+    fn below(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+
+}
+
+
+#[test]
+fn incremental_parses_match_full_parses() {
+    const PIECES: [&str; 9] = ["a", "bc", "1", "23", "=", ";", " ", "\n", "x = 4;"];
+    let parsed: Cell<usize> = Cell::new(0);
+    let mut random: Random = Random (0x2545_f491_4f6c_dd1d);
+    let mut text: Text = Text::from_string("a = 1; bc = 23;\nd = 4; e=5;");
+    for _ in 0..2000 {
+        let length: usize = text.as_str().len();
+        let start: usize = random.below(length + 1);
+        let end: usize = (start + random.below(4)).min(length);
+        let replacement: &str = if random.below(3) == 0 { "" } else { PIECES[random.below(9)] };
+        text.apply_edit(&TextEdit::new(Span::new(start, end), replacement));
+        let incremental: Result<Vec<(String, String)>, ()> = assignments(&parsed).parse(&text);
+        let full_text: Text = Text::from_string(text.as_str());
+        let full: Result<Vec<(String, String)>, ()> = assignments(&parsed).parse(&full_text);
+        assert_eq!(incremental, full, "after editing into {:?}", text.as_str());
+    }
+}
+
+#[test]
+fn incremental_parses_reuse_unedited_results() {
+    let parsed: Cell<usize> = Cell::new(0);
+    let mut text: Text = Text::from_string("a = 1; b = 2; c = 3; d = 4;");
+    assert_eq!(assignments(&parsed).parse(&text).map(|list| list.len()), Ok (4));
+    assert_eq!(parsed.replace(0), 4);
+    // only the edited assignment is parsed again, while the ones after it are moved
+    text.apply_edit(&TextEdit::new(Span::new(11, 12), "20"));
+    let reparsed: Vec<(String, String)> = assignments(&parsed).parse(&text).expect("text is valid");
+    assert_eq!(parsed.replace(0), 1);
+    assert_eq!(reparsed[1], ("b".to_string(), "20".to_string()));
+    assert_eq!(reparsed[3], ("d".to_string(), "4".to_string()));
+    // adding to the end only parses the new assignment
+    text.apply_edit(&TextEdit::insert(text.as_str().len(), " e = 5;"));
+    assert_eq!(assignments(&parsed).parse(&text).map(|list| list.len()), Ok (5));
+    assert_eq!(parsed.replace(0), 1);
+}
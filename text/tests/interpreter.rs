@@ -0,0 +1,125 @@
+// Copyright Rob Gage 2025
+
+use pups_core::{
+    Input,
+    prelude::*,
+};
+use pups_text::prelude::*;
+
+
+/// An arithmetic grammar that mixes PEG and EBNF notation
+const ARITHMETIC: &str = r#"
+    # sums and products of numbers
+    sum = product (_ ("+" / "-") _ product)* ;
+    product <- factor { _ "*" _ factor }
+    (* factors can be negated or parenthesized *)
+    factor ::= ['-' _] atom ;
+    atom = number | "(" , _ , sum , _ , ")" ;
+"#;
+
+/// Creates a tree of a rule
+fn tree(rule: &str, span: Span, children: Vec<ParseTree>) -> ParseTree {
+    ParseTree { children, rule: rule.to_string(), span }
+}
+
+/// Parses optional whitespace
+fn gap<'a>() -> impl Parser<'a, (), (), (), Text> { whitespace().or_not().map(|_| ()) }
+
+/// The hand-written equivalent of the `sum` rule
+fn sum(input: &Text) -> Result<ParseTree, ()> {
+    let operator = delimited(gap(), choice((token("+"), token("-"))), gap());
+    try_map(sequenced(product, repeated(preceded(operator, product))), |(first, rest), span| {
+        Ok (tree("sum", span, [first].into_iter().chain(rest).collect()))
+    }).parse(input)
+}
+
+/// The hand-written equivalent of the `product` rule
+fn product(input: &Text) -> Result<ParseTree, ()> {
+    let operator = delimited(gap(), token("*"), gap());
+    try_map(sequenced(factor, repeated(preceded(operator, factor))), |(first, rest), span| {
+        Ok (tree("product", span, [first].into_iter().chain(rest).collect()))
+    }).parse(input)
+}
+
+/// The hand-written equivalent of the `factor` rule
+fn factor(input: &Text) -> Result<ParseTree, ()> {
+    let negation = sequenced(token("-"), gap()).or_not();
+    try_map(preceded(negation, atom), |atom, span| Ok (tree("factor", span, vec![atom])))
+        .parse(input)
+}
+
+/// The hand-written equivalent of the `atom` rule
+fn atom(input: &Text) -> Result<ParseTree, ()> {
+    let parenthesized = delimited(sequenced(token("("), gap()), sum, sequenced(gap(), token(")")));
+    try_map(
+        choice((number().map(|_| Vec::new()), parenthesized.map(|sum| vec![sum]))),
+        |children, span| Ok (tree("atom", span, children)),
+    ).parse(input)
+}
+
+
+#[test]
+fn interpreted_parsers_match_hand_written_parsers() {
+    let grammar: RuntimeGrammar = RuntimeGrammar::load(ARITHMETIC).unwrap();
+    assert_eq!(grammar.rules().collect::<Vec<&str>>(), ["sum", "product", "factor", "atom"]);
+    for text in ["1", "1 + 2*3", "-(1 - 2) * - 3 + 4", "((7))", "1 +", "+ 1", "(1", "", "2 * x"] {
+        let (interpreted_input, hand_written_input): (Text, Text)
+            = (Text::from_string(text), Text::from_string(text));
+        let interpreted = grammar.parser("sum").unwrap();
+        assert_eq!(interpreted.parse(&interpreted_input), sum(&hand_written_input), "{text:?}");
+        assert_eq!(
+            interpreted_input.store_cursor(),
+            hand_written_input.store_cursor(),
+            "{text:?}"
+        );
+        interpreted_input.move_cursor(0);
+        hand_written_input.move_cursor(0);
+        assert_eq!(interpreted.check(&interpreted_input), sum(&hand_written_input).is_ok());
+    }
+}
+
+#[test]
+fn trees_have_rule_names_and_spans() {
+    let grammar: RuntimeGrammar = RuntimeGrammar::load(r#"
+        list <- "[" _ items? _ "]"
+        items <- word (_ "," _ word)*
+        word <- identifier
+    "#).unwrap();
+    let input: Text = Text::from_string("[a, bc]");
+    let parsed: ParseTree = grammar.parser("list").unwrap().parse(&input).unwrap();
+    assert_eq!(parsed, tree("list", Span::new(0, 7), vec![
+        tree("items", Span::new(1, 6), vec![
+            tree("word", Span::new(1, 2), Vec::new()),
+            tree("word", Span::new(4, 6), Vec::new()),
+        ]),
+    ]));
+    assert!(grammar.parser("missing").is_none());
+}
+
+#[test]
+fn invalid_grammars_are_reported() {
+    let diagnostics: Vec<Diagnostic> = RuntimeGrammar::load("a = b ; a = 'x' c ;").err().unwrap();
+    let messages: Vec<(&str, Span)> = diagnostics.iter()
+        .map(|diagnostic| (diagnostic.message.as_str(), diagnostic.span))
+        .collect();
+    assert_eq!(messages, [
+        ("undefined rule `b`", Span::new(4, 5)),
+        ("`a` is already defined", Span::new(8, 9)),
+        ("undefined rule `c`", Span::new(16, 17)),
+    ]);
+    let diagnostics: Vec<Diagnostic> = RuntimeGrammar::load("a = 'x' ; b = ( 'y' ;").err().unwrap();
+    assert_eq!(diagnostics[0].message, "expected a rule");
+    assert_eq!(diagnostics[0].span, Span::new(10, 11));
+}
+
+#[test]
+fn interpreted_parsers_are_observed_within() {
+    let source: &str = r#"greeting = "hello" _ ("world" / "there") ;"#;
+    let grammar: RuntimeGrammar = RuntimeGrammar::load(source).expect("the grammar is valid");
+    let text: Text = Text::from_string("hello ");
+    let parser = grammar.parser("greeting").expect("the rule is defined");
+    assert_eq!(
+        Completion::run(&parser, &text, 6),
+        [Expected::Token ("world".to_string()), Expected::Token ("there".to_string())]
+    );
+}
@@ -0,0 +1,77 @@
+// Copyright Rob Gage 2025
+
+use pups_core::{
+    Input,
+    prelude::*,
+};
+use pups_text::prelude::*;
+use std::cell::Cell;
+
+
+/// Parses a number as an owned string, memoized and counting how many times it is parsed anew
+fn memoized_number<'a>(parsed: &'a Cell<usize>) -> impl Parser<'a, String, (), String, Text> {
+    let number = number().map_messages(|()| unreachable!("numbers produce no messages"));
+    let warned = sequence((emit_message("number".to_string()), number))
+        .map(move |((), number): ((), &str)| {
+            parsed.set(parsed.get() + 1);
+            number.to_string()
+        });
+    labelled(warned, "number").memoize("number")
+}
+
+
+#[test]
+fn memoized_parsers_are_observed_by_nested_modes() {
+    let parsed: Cell<usize> = Cell::new(0);
+    let text: Text = Text::from_string("42");
+    let (result, tree) = Cst::run(&memoized_number(&parsed), &text);
+    assert!(result.is_success());
+    let kinds: Vec<&str> = tree.descendants().iter().map(SyntaxNode::kind).collect();
+    assert!(kinds.contains(&"number"), "{kinds:?}");
+    assert_eq!(parsed.replace(0), 1);
+}
+
+#[test]
+fn results_remembered_without_messages_are_not_reused_for_messages() {
+    let parsed: Cell<usize> = Cell::new(0);
+    let text: Text = Text::from_string("42");
+    let parser = memoized_number(&parsed);
+    assert_eq!(parser.parse(&text), Ok ("42".to_string()));
+    assert_eq!(parsed.replace(0), 1);
+    text.move_cursor(0);
+    assert_eq!(parser.parse(&text), Ok ("42".to_string()));
+    assert_eq!(parsed.replace(0), 0);
+    text.move_cursor(0);
+    let expected: (Result<String, ()>, Vec<String>)
+        = (Ok ("42".to_string()), vec!["number".to_string()]);
+    assert_eq!(parser.verbose(&text), expected);
+    assert_eq!(parsed.replace(0), 1);
+    text.move_cursor(0);
+    assert_eq!(parser.verbose(&text), expected);
+    text.move_cursor(0);
+    assert!(parser.check(&text));
+    assert_eq!(parsed.replace(0), 0);
+}
+
+#[test]
+fn observing_modes_apply_memoized_parsers_on_every_run() {
+    let parsed: Cell<usize> = Cell::new(0);
+    let text: Text = Text::from_string("42");
+    let parser = memoized_number(&parsed);
+    let kinds = |tree: SyntaxNode| -> Vec<String> {
+        tree.descendants().iter().map(|node| node.kind().to_string()).collect()
+    };
+    let (_, first) = Cst::run(&parser, &text);
+    text.move_cursor(0);
+    let (_, second) = Cst::run(&parser, &text);
+    assert_eq!(kinds(first), kinds(second));
+    assert_eq!(parsed.replace(0), 2);
+    let highlighted = number().emit(()).highlight(HighlightClass::Number).memoize("highlighted");
+    text.move_cursor(0);
+    assert_eq!(highlighted.parse(&text), Ok (()));
+    for _ in 0..2 {
+        text.move_cursor(0);
+        let highlights: Vec<(Span, HighlightClass)> = Highlight::run(&highlighted, &text);
+        assert_eq!(highlights, vec![(Span::new(0, 2), HighlightClass::Number)]);
+    }
+}
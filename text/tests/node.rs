@@ -0,0 +1,81 @@
+// Copyright Rob Gage 2025
+
+use pups_core::prelude::*;
+use pups_text::prelude::*;
+use std::cell::Cell;
+
+
+/// Parses optional whitespace
+fn gap<'a>() -> impl Parser<'a, (), (), (), Text> { whitespace().or_not().map(|_| ()) }
+
+/// Parses `[item, ...]` lists of numbers and `name=number` bindings into nodes
+fn list<'a>() -> impl Parser<'a, Node<&'a str>, (), (), Text> {
+    let binding = node("binding", sequenced(
+        leaf("name", unicode_identifier::<_, Text>()),
+        preceded(token("="), leaf("number", number::<_, Text>())),
+    ));
+    let item = choice((binding, leaf("number", number::<_, Text>())));
+    node("list", delimited(
+        token("["),
+        separated(delimited(gap(), item, gap()), token(",")),
+        token("]"),
+    ))
+}
+
+/// Creates a node
+fn tree<'a>(rule: &'static str, span: (usize, usize), text: &'a str, children: Vec<Node<&'a str>>)
+    -> Node<&'a str>
+{
+    Node { children, rule, span: Span::new(span.0, span.1), text }
+}
+
+
+#[test]
+fn nodes_collect_children_across_sequences_choices_and_repetitions() {
+    let input: Text = Text::from_string("[1, x=2, []]");
+    assert_eq!(list().parse(&input), Err (()));
+    let input: Text = Text::from_string("[1, x=2 ]");
+    assert_eq!(list().parse(&input), Ok (tree("list", (0, 9), "[1, x=2 ]", vec![
+        tree("number", (1, 2), "1", Vec::new()),
+        tree("binding", (4, 7), "x=2", vec![
+            tree("name", (4, 5), "x", Vec::new()),
+            tree("number", (6, 7), "2", Vec::new()),
+        ]),
+    ])));
+    let input: Text = Text::from_string("[]");
+    assert_eq!(list().parse(&input), Ok (tree("list", (0, 2), "[]", Vec::new())));
+}
+
+#[test]
+fn trees_are_printed_one_node_per_line() {
+    let input: Text = Text::from_string("[a=1]");
+    assert_eq!(
+        list().parse(&input).unwrap().to_string(),
+        "list [0..5] \"[a=1]\"\n\
+        \x20 binding [1..4] \"a=1\"\n\
+        \x20   name [1..2] \"a\"\n\
+        \x20   number [3..4] \"1\"\n"
+    );
+}
+
+thread_local! {
+    /// The number of times that a `Counted` output was collected into a tree
+    static COLLECTED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// An output that counts how many times it is collected into a tree
+struct Counted;
+
+impl<S> IntoChildren<S> for Counted {
+    fn add_children(self, _: &mut Vec<Node<S>>) { COLLECTED.set(COLLECTED.get() + 1) }
+}
+
+#[test]
+fn checking_input_does_not_build_trees() {
+    let (checked, parsed): (Text, Text) = (Text::from_string("a"), Text::from_string("a"));
+    let counted = node("counted", token("a").map(|_| Counted));
+    assert!(counted.check(&checked));
+    assert_eq!(COLLECTED.get(), 0);
+    assert!(counted.parse(&parsed).is_ok());
+    assert_eq!(COLLECTED.get(), 1);
+}
@@ -0,0 +1,108 @@
+// Copyright Rob Gage 2025
+
+use pups_core::prelude::*;
+use pups_text::prelude::*;
+
+
+/// A record with named fields
+#[derive(Clone, Debug, PartialEq)]
+struct Record {
+    fields: Vec<Field>,
+    name: String,
+}
+
+/// A named field of a record
+#[derive(Clone, Debug, PartialEq)]
+struct Field {
+    name: String,
+    value: Value,
+}
+
+/// The value of a field
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Name (String),
+    Number (String),
+}
+
+/// Parses and prints `record Name { field = value, ... }`
+fn record<'a>() -> impl Parser<'a, Record, (), (), Text> + Printer<Record> {
+    let gap = || whitespace().or_not();
+    let space = || gap().format(|_| Doc::text(" "));
+    let line = || gap().format(|_| Doc::line());
+    let value = choice((
+        number().map_invertible(
+            |number: &str| Value::Number (number.to_string()),
+            |value: &Value| match value {
+                Value::Number (number) => Some (number.clone()),
+                Value::Name (_) => None,
+            },
+        ),
+        unicode_identifier().map_invertible(
+            |name: &str| Value::Name (name.to_string()),
+            |value: &Value| match value {
+                Value::Name (name) => Some (name.clone()),
+                Value::Number (_) => None,
+            },
+        ),
+    ));
+    let field = terminated(unicode_identifier(), delimited(space(), token("="), space()))
+        .then(value)
+        .map_invertible(
+            |(name, value): (&str, Value)| Field { name: name.to_string(), value },
+            |field: &Field| Some ((field.name.clone(), field.value.clone())),
+        );
+    let fields = separated(field, delimited(gap(), token(","), line()));
+    let body = delimited(
+        token("{"),
+        preceded(line(), fields).format(|doc| Doc::nest(4, doc)),
+        preceded(line(), token("}")),
+    );
+    let keyword = terminated(token("record"), whitespace().format(|_| Doc::text(" ")));
+    let header = preceded(keyword, unicode_identifier());
+    header.then(preceded(space(), body))
+        .format(Doc::group)
+        .map_invertible(
+            |(name, fields): (&str, Vec<Field>)| Record { fields, name: name.to_string() },
+            |record: &Record| Some ((record.name.clone(), record.fields.clone())),
+        )
+}
+
+
+#[test]
+fn printed_outputs_are_laid_out_to_fit() {
+    let text: Text = Text::from_string("record  Point{x=1 ,\n y   =   origin}");
+    let point: Record = record().parse(&text).expect("the record is valid");
+    assert_eq!(record().pretty(&point, 80).unwrap(), "record Point { x = 1, y = origin }");
+    assert_eq!(
+        record().pretty(&point, 20).unwrap(),
+        "record Point {\n    x = 1,\n    y = origin\n}"
+    );
+}
+
+#[test]
+fn printed_outputs_parse_back_into_the_same_outputs() {
+    let point: Record = Record {
+        fields: vec![
+            Field { name: "x".into(), value: Value::Number ("1".into()) },
+            Field { name: "y".into(), value: Value::Name ("origin".into()) },
+        ],
+        name: "Point".into(),
+    };
+    for width in [10, 40, 80] {
+        let printed: String = record().pretty(&point, width).expect("the record is printable");
+        let text: Text = Text::from_string(&printed);
+        assert_eq!(record().parse(&text), Ok (point.clone()));
+    }
+}
+
+#[test]
+fn outputs_that_could_not_be_parsed_are_not_printed() {
+    let invalid_name: Record = Record { fields: Vec::new(), name: "1st".into() };
+    assert_eq!(record().print(&invalid_name), None);
+    let invalid_value: Record = Record {
+        fields: vec![Field { name: "x".into(), value: Value::Number ("one".into()) }],
+        name: "Point".into(),
+    };
+    assert_eq!(record().print(&invalid_value), None);
+}